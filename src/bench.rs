@@ -0,0 +1,228 @@
+use crate::{
+    error::{Error, ParseError, Result},
+    parse, Solution,
+};
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    hint::black_box,
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        })
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "part1" => Ok(Stage::Part1),
+            "part2" => Ok(Stage::Part2),
+            _ => Err(format!("unknown stage `{}`", s)),
+        }
+    }
+}
+
+// Every sample taken of one stage of one day.
+pub struct Timing {
+    pub day: u32,
+    pub stage: Stage,
+    samples: Vec<Duration>,
+}
+
+impl Timing {
+    fn new(day: u32, stage: Stage, mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        Self {
+            day,
+            stage,
+            samples,
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        let mid = self.samples.len() / 2;
+        if self.samples.len().is_multiple_of(2) {
+            (self.samples[mid - 1] + self.samples[mid]) / 2
+        } else {
+            self.samples[mid]
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+}
+
+// Run every stage of `S` against `input` `iterations` times.  Each
+// iteration parses afresh, so the parse timings are independent samples
+// too.
+pub fn measure<S: Solution>(day: u32, input: &str, iterations: usize) -> Result<Vec<Timing>> {
+    let iterations = iterations.max(1);
+    let mut samples: Vec<Vec<Duration>> = (0..3).map(|_| Vec::with_capacity(iterations)).collect();
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = black_box(S::parse(black_box(input))?);
+        samples[0].push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&parsed));
+        samples[1].push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(&parsed));
+        samples[2].push(start.elapsed());
+    }
+
+    Ok(Stage::ALL
+        .iter()
+        .zip(samples)
+        .map(|(&stage, samples)| Timing::new(day, stage, samples))
+        .collect())
+}
+
+pub enum Comparison {
+    // There is no baseline for this day and stage.
+    New,
+    Unchanged(f64),
+    Improved(f64),
+    Regressed(f64),
+}
+
+// The median timings of a previous benchmark run, stored one per line as
+// `day stage nanoseconds`.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: HashMap<(u32, Stage), Duration>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).map_err(|source| Error::Input {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&contents).map_err(|err| Error::from(err).in_file(path))
+    }
+
+    fn parse(contents: &str) -> Result<Self, ParseError> {
+        let entries = parse::lines(contents, |line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 3 {
+                return Err(ParseError::new(line, 1, "expected `day stage nanoseconds`"));
+            }
+            let day = parse::field(line, fields[0], "invalid day")?;
+            let stage = parse::field(line, fields[1], "invalid stage")?;
+            let nanos = parse::field(line, fields[2], "invalid duration")?;
+            Ok(((day, stage), Duration::from_nanos(nanos)))
+        })?;
+        Ok(Self {
+            medians: entries.into_iter().collect(),
+        })
+    }
+
+    pub fn save(timings: &[Timing], path: &Path) -> Result<()> {
+        let contents: String = timings
+            .iter()
+            .map(|t| format!("{} {} {}\n", t.day, t.stage, t.median().as_nanos()))
+            .collect();
+        fs::write(path, contents).map_err(|source| Error::Output {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    // Compare the median of `timing` with the baseline.  A change of less
+    // than `threshold` (a fraction, e.g. 0.1 for 10%) counts as noise.
+    pub fn compare(&self, timing: &Timing, threshold: f64) -> Comparison {
+        let baseline = match self.medians.get(&(timing.day, timing.stage)) {
+            Some(baseline) => baseline.as_secs_f64(),
+            None => return Comparison::New,
+        };
+        let change = (timing.median().as_secs_f64() - baseline) / baseline;
+        if change > threshold {
+            Comparison::Regressed(change)
+        } else if change < -threshold {
+            Comparison::Improved(change)
+        } else {
+            Comparison::Unchanged(change)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(stage: Stage, millis: &[u64]) -> Timing {
+        let samples = millis.iter().map(|&ms| Duration::from_millis(ms)).collect();
+        Timing::new(1, stage, samples)
+    }
+
+    #[test]
+    fn statistics() {
+        let t = timing(Stage::Parse, &[5, 1, 3]);
+        assert_eq!(t.median(), Duration::from_millis(3));
+        assert_eq!(t.min(), Duration::from_millis(1));
+        assert_eq!(t.max(), Duration::from_millis(5));
+        assert_eq!(
+            timing(Stage::Parse, &[4, 1, 2, 3]).median(),
+            Duration::from_micros(2500)
+        );
+    }
+
+    #[test]
+    fn comparison() {
+        let baseline = Baseline::parse("1 parse 10000000\n1 part1 10000000\n").unwrap();
+        let threshold = 0.1;
+        assert!(matches!(
+            baseline.compare(&timing(Stage::Parse, &[12]), threshold),
+            Comparison::Regressed(_)
+        ));
+        assert!(matches!(
+            baseline.compare(&timing(Stage::Part1, &[8]), threshold),
+            Comparison::Improved(_)
+        ));
+        assert!(matches!(
+            baseline.compare(&timing(Stage::Part1, &[10]), threshold),
+            Comparison::Unchanged(_)
+        ));
+        assert!(matches!(
+            baseline.compare(&timing(Stage::Part2, &[10]), threshold),
+            Comparison::New
+        ));
+    }
+
+    #[test]
+    fn malformed_baseline() {
+        let err = Baseline::parse("1 parse 10\n1 part3 10\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+}
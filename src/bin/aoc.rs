@@ -0,0 +1,561 @@
+use advent_2020::{
+    bench::{Baseline, Comparison},
+    days::{
+        self,
+        day_7::{self, BagGraph},
+        day_8::{self, Profile, Trace},
+    },
+    error::Error,
+    input,
+    ledger::{self, HashingReader, Ledger, Status},
+    runner::{self, Day},
+    scaffold, Part,
+};
+use std::{
+    env,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+
+const USAGE: &str = "usage: aoc [DAY [PART]] [--input PATH] [--format text|json]
+                        [--ledger PATH] [--pin]
+       aoc bench [DAY] [--input PATH] [--iterations N] [--baseline PATH]
+                 [--save PATH] [--threshold PERCENT]
+       aoc new DAY
+       aoc debug [--input PATH]
+       aoc trace [--input PATH] [--repair] [--save PATH]
+       aoc trace replay FILE
+       aoc trace diff [FILE FILE]
+       aoc analyze [--input PATH]
+       aoc profile [--input PATH] [--repair] [--fuel N]
+       aoc bags QUERY [--input PATH]
+       aoc bags dot|mermaid [--input PATH] [--focus BAG] [--related]
+
+The input defaults to data/day_DAY.txt and can also be set with the
+AOC_INPUT environment variable.  A custom input requires a DAY; an input
+of `-` reads it from stdin.
+
+With --format json each answer is printed as a JSON object on its own
+line, with its elapsed time in nanoseconds and any debug output.  In text
+mode debug output goes to stderr.
+
+Each answer is checked against the ledger of accepted answers (default
+data/ledger.txt) for the same day, part and input, and marked correct,
+wrong or unknown; the command fails if any answer is wrong, or if a part
+finds no answer in the input.  --pin records this run's answers in the
+ledger as accepted.
+
+`bench` times parsing and each part separately over N runs (default 10)
+and reports the median, minimum and maximum.  With --baseline the medians
+are compared to a file written by a previous --save, and the command
+fails if any stage got slower by more than PERCENT (default 10).
+Build with --release for meaningful numbers.
+
+`new` adds a solution module for DAY to src/days with an example test to
+fill in, creates an empty data/day_DAY.txt and registers the day with the
+runner.  Run it from the repository root.
+
+`debug` steps through day 8's boot code, reading debugger commands from
+stdin; type `help` at its prompt for the commands.  The program may use
+labels and comments as well as the puzzle's format.  An empty line repeats
+the previous command.
+
+`profile` runs day 8's boot code for at most N steps (default 10000),
+without stopping at its loop, and lists how often each instruction ran
+and what it added to the accumulator.
+
+`bags` answers a question about day 7's bag rules; run `aoc bags help`
+for the questions it knows.  Bag names are two words and need no quotes,
+as in `aoc bags holders shiny gold`.
+
+`bags dot` and `bags mermaid` draw the rules as a graph, with edges
+labelled by quantity.  --focus highlights a bag, as in `--focus shiny
+gold`, with the bags which hold it and those it holds, and --related
+leaves out every other bag.
+
+Before answering, `bags` checks the rules and reports every bag named
+without a rule of its own, every bag with more than one rule, every
+member with a quantity of 0 and any bag which would hold itself.";
+
+enum Command {
+    Run,
+    Bench,
+    New,
+    Debug,
+    Trace,
+    Analyze,
+    Profile,
+    Bags,
+}
+
+enum Format {
+    Text,
+    Json,
+}
+
+struct Args {
+    command: Command,
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<PathBuf>,
+    format: Format,
+    ledger: PathBuf,
+    pin: bool,
+    iterations: usize,
+    baseline: Option<PathBuf>,
+    save: Option<PathBuf>,
+    threshold: f64,
+    repair: bool,
+    fuel: usize,
+    focus: Option<String>,
+    related: bool,
+    // The words after a subcommand which takes them, like `trace`.
+    operands: Vec<String>,
+}
+
+impl Args {
+    fn parse(args: impl Iterator<Item = String>) -> Self {
+        let mut result = Args {
+            command: Command::Run,
+            day: None,
+            part: None,
+            input: None,
+            format: Format::Text,
+            ledger: PathBuf::from(ledger::DEFAULT_PATH),
+            pin: false,
+            iterations: 10,
+            baseline: None,
+            save: None,
+            threshold: 10.0,
+            repair: false,
+            fuel: 10_000,
+            focus: None,
+            related: false,
+            operands: vec![],
+        };
+        let mut args = args.peekable();
+
+        match args.peek().map(String::as_str) {
+            Some("bench") => result.command = Command::Bench,
+            Some("new") => result.command = Command::New,
+            Some("debug") => result.command = Command::Debug,
+            Some("trace") => result.command = Command::Trace,
+            Some("analyze") => result.command = Command::Analyze,
+            Some("profile") => result.command = Command::Profile,
+            Some("bags") => result.command = Command::Bags,
+            _ => {}
+        }
+        if !matches!(result.command, Command::Run) {
+            args.next();
+        }
+
+        while let Some(arg) = args.next() {
+            let mut value = || match args.next() {
+                Some(value) => value,
+                None => fail(&format!("{} requires a value", arg)),
+            };
+            match arg.as_str() {
+                "-i" | "--input" => result.input = Some(value().into()),
+                "-f" | "--format" => {
+                    result.format = match value().as_str() {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        other => fail(&format!("Unknown format {:?}", other)),
+                    }
+                }
+                "--ledger" => result.ledger = value().into(),
+                "--pin" => result.pin = true,
+                "-n" | "--iterations" => result.iterations = parse_number(&value(), "iterations"),
+                "--baseline" => result.baseline = Some(value().into()),
+                "--save" => result.save = Some(value().into()),
+                "--threshold" => result.threshold = parse_number(&value(), "threshold"),
+                "--repair" => result.repair = true,
+                "--fuel" => result.fuel = parse_number(&value(), "fuel"),
+                "--focus" => {
+                    // A bag name is two words, quoted or not.
+                    let mut focus = value();
+                    if focus.split_whitespace().count() == 1 {
+                        match args.next() {
+                            Some(colour) => focus = format!("{} {}", focus, colour),
+                            None => fail("--focus expects a bag name of two words"),
+                        }
+                    }
+                    result.focus = Some(focus);
+                }
+                "--related" => result.related = true,
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    process::exit(0);
+                }
+                _ if matches!(result.command, Command::Trace | Command::Bags) => {
+                    result.operands.push(arg)
+                }
+                _ if result.day.is_none() => result.day = Some(parse_number(&arg, "day")),
+                _ if result.part.is_none() => result.part = Some(parse_number(&arg, "part")),
+                _ => fail(&format!("Unexpected argument {:?}", arg)),
+            }
+        }
+
+        result
+    }
+
+    fn days(&self) -> Vec<&'static Day> {
+        match self.day {
+            Some(n) => match runner::find(n) {
+                Some(day) => vec![day],
+                None => fail(&format!("No solution for day {}", n)),
+            },
+            None if self.input.is_some() || env::var_os(input::INPUT_VAR).is_some() => {
+                fail("A custom input can only be used when running a single day")
+            }
+            None => days::ALL.iter().collect(),
+        }
+    }
+
+    fn read_input(&self, day: &Day) -> (PathBuf, String) {
+        let path = input::resolve(day.number, self.input.as_deref());
+        match input::read(&path) {
+            Ok(input) => (path, input),
+            Err(err) => exit_with(err),
+        }
+    }
+}
+
+fn main() {
+    let args = Args::parse(env::args().skip(1));
+
+    match args.command {
+        Command::Run => run(&args),
+        Command::Bench => bench(&args),
+        Command::New => new(&args),
+        Command::Debug => debug(&args),
+        Command::Trace => trace(&args),
+        Command::Analyze => analyze(&args),
+        Command::Profile => profile(&args),
+        Command::Bags => bags(&args),
+    }
+}
+
+fn run(args: &Args) {
+    let parts = match args.part {
+        Some(n) => match Part::from_number(n) {
+            Some(part) => vec![part],
+            None => fail(&format!("Part must be 1 or 2, got {}", n)),
+        },
+        None => Part::ALL.to_vec(),
+    };
+
+    let mut ledger = Ledger::load(&args.ledger).unwrap_or_else(|err| exit_with(err));
+    let mut wrong_answers = 0;
+    let mut missing_answers = 0;
+
+    for day in args.days() {
+        let path = input::resolve(day.number, args.input.as_deref());
+        let mut reader = input::open(&path)
+            .map(HashingReader::new)
+            .unwrap_or_else(|err| exit_with(err));
+        let answers = day
+            .run(&mut reader, &parts)
+            .unwrap_or_else(|err| exit_with(err.in_file(&path)));
+        let input_hash = reader
+            .finish()
+            .unwrap_or_else(|err| exit_with(Error::Io(err).in_file(&path)));
+
+        for answer in answers {
+            let status = match &answer.value {
+                None => {
+                    missing_answers += 1;
+                    Status::Unknown
+                }
+                Some(value) if args.pin => {
+                    ledger.pin(answer.day, answer.part, input_hash, value);
+                    Status::Correct
+                }
+                Some(value) => ledger.check(answer.day, answer.part, input_hash, value),
+            };
+            if let Status::Wrong { .. } = status {
+                wrong_answers += 1;
+            }
+
+            match args.format {
+                Format::Text => {
+                    for line in &answer.debug {
+                        eprintln!("{}", line);
+                    }
+                    match &answer.value {
+                        Some(value) => println!(
+                            "Day {} part {}: {} ({})",
+                            answer.day, answer.part, value, status
+                        ),
+                        None => println!("Day {} part {}: no answer", answer.day, answer.part),
+                    }
+                }
+                Format::Json => println!("{}", answer.to_json(&status)),
+            }
+        }
+    }
+
+    if args.pin {
+        if let Err(err) = ledger.save(&args.ledger) {
+            exit_with(err);
+        }
+    }
+
+    if missing_answers > 0 {
+        eprintln!("{} part(s) found no answer in the input", missing_answers);
+    }
+    if wrong_answers > 0 {
+        eprintln!("{} answer(s) differ from the ledger", wrong_answers);
+    }
+    if missing_answers > 0 || wrong_answers > 0 {
+        process::exit(1);
+    }
+}
+
+fn bench(args: &Args) {
+    if args.part.is_some() {
+        fail("bench always times parsing and both parts");
+    }
+    if let Format::Json = args.format {
+        fail("bench only supports text output");
+    }
+    let baseline = match &args.baseline {
+        Some(path) => Baseline::load(path).unwrap_or_else(|err| exit_with(err)),
+        None => Baseline::default(),
+    };
+    let threshold = args.threshold / 100.0;
+
+    println!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}  vs baseline",
+        "day", "stage", "median", "min", "max"
+    );
+
+    let mut timings = vec![];
+    let mut regressions = 0;
+    for day in args.days() {
+        let (path, input) = args.read_input(day);
+        let day_timings = day
+            .measure(&input, args.iterations)
+            .unwrap_or_else(|err| exit_with(err.in_file(&path)));
+
+        for timing in &day_timings {
+            let comparison = match baseline.compare(timing, threshold) {
+                Comparison::New => String::from("-"),
+                Comparison::Unchanged(change) => format!("{:+.1}%", change * 100.0),
+                Comparison::Improved(change) => format!("{:+.1}% improved", change * 100.0),
+                Comparison::Regressed(change) => {
+                    regressions += 1;
+                    format!("{:+.1}% REGRESSED", change * 100.0)
+                }
+            };
+            println!(
+                "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}  {}",
+                timing.day,
+                timing.stage,
+                format_duration(timing.median()),
+                format_duration(timing.min()),
+                format_duration(timing.max()),
+                comparison
+            );
+        }
+        timings.extend(day_timings);
+    }
+
+    if let Some(path) = &args.save {
+        if let Err(err) = Baseline::save(&timings, path) {
+            exit_with(err);
+        }
+    }
+
+    if regressions > 0 {
+        eprintln!("{} stage(s) regressed", regressions);
+        process::exit(1);
+    }
+}
+
+fn new(args: &Args) {
+    let day = match (args.day, args.part) {
+        (Some(day), None) => day,
+        _ => fail(USAGE),
+    };
+    match scaffold::new_day(Path::new("."), day) {
+        Ok(changed) => {
+            for path in changed {
+                println!("{}", path.display());
+            }
+        }
+        Err(err) => exit_with(err),
+    }
+}
+
+fn debug(args: &Args) {
+    if args.day.is_some() {
+        fail("debug always runs day 8's boot code");
+    }
+    if input::resolve(8, args.input.as_deref()) == Path::new(input::STDIN) {
+        fail("debugger commands are read from stdin, so the program must be a file");
+    }
+    let program = handheld_program(args, false);
+
+    let mut debugger = day_8::Debugger::new(&program);
+    println!("{}", debugger.position());
+
+    let stdin = io::stdin();
+    let mut previous = String::new();
+    loop {
+        print!("(debug) ");
+        io::stdout()
+            .flush()
+            .unwrap_or_else(|err| exit_with(Error::Io(err)));
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(err) => exit_with(Error::Io(err)),
+        }
+        let line = match line.trim() {
+            "" => previous.clone(),
+            line => line.to_string(),
+        };
+
+        match line.as_str() {
+            "" => continue,
+            "q" | "quit" => break,
+            "h" | "help" => println!("{}", day_8::HELP),
+            _ => match line.parse::<day_8::Command>() {
+                Ok(command) => println!("{}", debugger.execute(&command)),
+                Err(err) => println!("{}", err),
+            },
+        }
+        previous = line;
+    }
+}
+
+fn trace(args: &Args) {
+    let record = |repair| Trace::record(&handheld_program(args, repair), usize::MAX);
+    let load = |path: &str| Trace::load(Path::new(path)).unwrap_or_else(|err| exit_with(err));
+
+    let operands: Vec<&str> = args.operands.iter().map(String::as_str).collect();
+    match operands.as_slice() {
+        [] => {
+            let trace = record(args.repair);
+            match &args.save {
+                Some(path) => trace.save(path).unwrap_or_else(|err| exit_with(err)),
+                None => print!("{}", trace.listing()),
+            }
+        }
+        ["replay", path] => print!("{}", load(path).listing()),
+        ["diff"] => print!("{}", record(false).diff(&record(true))),
+        ["diff", left, right] => print!("{}", load(left).diff(&load(right))),
+        _ => fail(USAGE),
+    }
+}
+
+fn analyze(args: &Args) {
+    if args.day.is_some() {
+        fail("analyze always reports on day 8's boot code");
+    }
+    print!("{}", day_8::analyze(&handheld_program(args, false)));
+}
+
+fn profile(args: &Args) {
+    if args.day.is_some() {
+        fail("profile always runs day 8's boot code");
+    }
+    let program = handheld_program(args, args.repair);
+    print!("{}", Profile::record(&program, args.fuel));
+}
+
+fn bags(args: &Args) {
+    if args.day.is_some() {
+        fail("bags always reads day 7's rules");
+    }
+    enum Task {
+        Export(day_7::Export),
+        Query(day_7::Query),
+    }
+
+    let operands: Vec<&str> = args.operands.iter().map(String::as_str).collect();
+    let task = match operands.as_slice() {
+        [] => fail(USAGE),
+        ["help"] => {
+            println!("{}", day_7::HELP);
+            return;
+        }
+        [format @ "dot"] | [format @ "mermaid"] => {
+            if args.related && args.focus.is_none() {
+                fail("--related requires --focus");
+            }
+            Task::Export(day_7::Export {
+                format: format.parse().unwrap_or_else(|err: String| fail(&err)),
+                focus: args.focus.as_deref().map(Into::into),
+                related_only: args.related,
+            })
+        }
+        _ => Task::Query(
+            operands
+                .join(" ")
+                .parse()
+                .unwrap_or_else(|err: String| fail(&err)),
+        ),
+    };
+
+    let path = input::resolve(7, args.input.as_deref());
+    let input = input::read(&path).unwrap_or_else(|err| exit_with(err.in_file(&path)));
+    let rules = day_7::validate(&input).unwrap_or_else(|problems| {
+        for problem in problems {
+            eprintln!("error: {}", problem.in_file(&path));
+        }
+        process::exit(1);
+    });
+    let graph = BagGraph::new(&rules).expect("parse rejects cyclic rules");
+    if let Task::Export(day_7::Export {
+        focus: Some(focus), ..
+    }) = &task
+    {
+        if graph.find(focus).is_none() {
+            fail(&format!("no rule mentions {}", focus));
+        }
+    }
+    match task {
+        Task::Export(export) => print!("{}", day_7::export(&graph, &export)),
+        Task::Query(query) => println!("{}", query.answer(&graph)),
+    }
+}
+
+// The day 8 program to debug, trace or profile, with the part 2 fix applied if
+// `repair` is set.
+fn handheld_program(args: &Args, repair: bool) -> Vec<day_8::Instruction> {
+    let path = input::resolve(8, args.input.as_deref());
+    let program = input::read(&path)
+        .and_then(|input| Ok(day_8::assemble(&input)?))
+        .unwrap_or_else(|err| exit_with(err.in_file(&path)));
+    if !repair {
+        return program;
+    }
+    match day_8::repair(&program) {
+        Some(fix) => fix.apply(&program),
+        None => fail("the program can't be made to terminate by flipping one instruction"),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3?}", duration)
+}
+
+fn parse_number<T: std::str::FromStr>(s: &str, name: &str) -> T {
+    s.parse()
+        .unwrap_or_else(|_| fail(&format!("Invalid {} {:?}\n{}", name, s, USAGE)))
+}
+
+fn exit_with(err: Error) -> ! {
+    eprintln!("error: {}", err);
+    process::exit(1);
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
@@ -0,0 +1,85 @@
+use crate::{
+    error::{ParseError, Result},
+    parse, Solution,
+};
+use std::io::BufRead;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input, parse_expense)?)
+    }
+
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse::read_lines(reader, parse_expense)
+    }
+
+    fn part1(expenses: &Self::Input) -> Option<i32> {
+        part1(expenses)
+    }
+
+    fn part2(expenses: &Self::Input) -> Option<i32> {
+        part2(expenses)
+    }
+}
+
+fn parse_expense(line: &str) -> Result<i32, ParseError> {
+    parse::field(line, line, "invalid expense")
+}
+
+fn part1(expenses: &[i32]) -> Option<i32> {
+    for i in 0..expenses.len() {
+        for j in (i + 1)..expenses.len() {
+            if expenses[i] + expenses[j] == 2020 {
+                return Some(expenses[i] * expenses[j]);
+            }
+        }
+    }
+    None
+}
+
+fn part2(expenses: &[i32]) -> Option<i32> {
+    for i in 0..expenses.len() {
+        for j in (i + 2)..expenses.len() {
+            for k in (i + 1)..j {
+                if expenses[i] + expenses[j] + expenses[k] == 2020 {
+                    return Some(expenses[i] * expenses[j] * expenses[k]);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1721
+979
+366
+299
+675
+1456
+";
+
+    #[test]
+    fn example() {
+        let expenses = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part1(&expenses), Some(514579));
+        assert_eq!(Day1::part2(&expenses), Some(241861950));
+    }
+
+    #[test]
+    fn input() {
+        let expenses = Day1::parse(include_str!("../../data/day_1.txt")).unwrap();
+        assert_eq!(Day1::part1(&expenses), Some(866436));
+        assert_eq!(Day1::part2(&expenses), Some(276650720));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{
+    error::{ParseError, Result},
+    grid::{Grid, Offset, Position},
+    Solution,
+};
+use std::fmt;
+
+#[derive(PartialEq, Clone, Copy)]
+enum Seat {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl Seat {
+    fn from_char(chr: char) -> Option<Self> {
+        match chr {
+            '.' => Some(Seat::Floor),
+            'L' => Some(Seat::Empty),
+            '#' => Some(Seat::Occupied),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Seat::Floor => '.',
+            Seat::Empty => 'L',
+            Seat::Occupied => '#',
+        }
+    }
+}
+
+impl fmt::Debug for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Seating {
+    seats: Grid<Seat>,
+}
+
+impl Seating {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let seats = Grid::parse(s, Seat::from_char, "expected `.`, `L` or `#`")?;
+        Ok(Self { seats })
+    }
+
+    fn neighbors(&self, pos: Position) -> Vec<&Seat> {
+        self.seats.neighbors(pos).map(|(_, seat)| seat).collect()
+    }
+
+    fn neighbors_part_2(&self, pos: Position) -> Vec<&Seat> {
+        Offset::NEIGHBORS
+            .iter()
+            .flat_map(|&direction| {
+                self.seats
+                    .cast(pos, direction)
+                    .map(|(_, seat)| seat)
+                    .find(|&seat| seat == &Seat::Empty || seat == &Seat::Occupied)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for Seating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.seats.render(|seat| seat.to_char()))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Rules {
+    Adjacent,
+    LineOfSight,
+}
+
+impl Rules {
+    fn neighbors<'a>(&self, seating: &'a Seating, pos: Position) -> Vec<&'a Seat> {
+        match self {
+            Rules::Adjacent => seating.neighbors(pos),
+            Rules::LineOfSight => seating.neighbors_part_2(pos),
+        }
+    }
+
+    // The number of occupied neighbors at which an occupied seat is vacated.
+    fn tolerance(&self) -> usize {
+        match self {
+            Rules::Adjacent => 4,
+            Rules::LineOfSight => 5,
+        }
+    }
+}
+
+impl Seating {
+    fn evolve(self, rules: Rules) -> SeatingIter {
+        SeatingIter {
+            current_seating: self,
+            rules,
+        }
+    }
+}
+
+struct SeatingIter {
+    current_seating: Seating,
+    rules: Rules,
+}
+
+impl Iterator for SeatingIter {
+    type Item = Seating;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current_seating = &self.current_seating;
+        let next_seats = current_seating.seats.map(|pos, seat| {
+            let neighbors = self.rules.neighbors(current_seating, pos);
+            let occupied_neighbors_count = neighbors
+                .iter()
+                .filter(|seat| matches!(seat, Seat::Occupied))
+                .count();
+
+            match &seat {
+                Seat::Floor => Seat::Floor,
+                Seat::Empty => {
+                    if occupied_neighbors_count > 0 {
+                        Seat::Empty
+                    } else {
+                        Seat::Occupied
+                    }
+                }
+                Seat::Occupied => {
+                    if occupied_neighbors_count >= self.rules.tolerance() {
+                        Seat::Empty
+                    } else {
+                        Seat::Occupied
+                    }
+                }
+            }
+        });
+
+        let next_seating = Seating { seats: next_seats };
+
+        Some(std::mem::replace(&mut self.current_seating, next_seating))
+    }
+}
+
+fn stable_occupied_count(initial_seating: &Seating, rules: Rules) -> usize {
+    let mut previous_seating = None;
+    for seating in initial_seating.clone().evolve(rules) {
+        if let Some(previous_seating) = &previous_seating {
+            if seating == *previous_seating {
+                break;
+            }
+        }
+        previous_seating = Some(seating)
+    }
+
+    previous_seating
+        .unwrap()
+        .seats
+        .iter()
+        .filter(|(_, s)| s == &&Seat::Occupied)
+        .count()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Seating;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Seating::from_str(input)?)
+    }
+
+    fn part1(seating: &Seating) -> Option<usize> {
+        Some(stable_occupied_count(seating, Rules::Adjacent))
+    }
+
+    fn part2(seating: &Seating) -> Option<usize> {
+        Some(stable_occupied_count(seating, Rules::LineOfSight))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL
+";
+
+    #[test]
+    fn example() {
+        let seating = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part1(&seating), Some(37));
+        assert_eq!(Day11::part2(&seating), Some(26));
+    }
+
+    #[test]
+    fn input() {
+        let seating = Day11::parse(include_str!("../../data/day_11.txt")).unwrap();
+        assert_eq!(Day11::part1(&seating), Some(2303));
+        assert_eq!(Day11::part2(&seating), Some(2057));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{
+    error::{ParseError, Result},
+    parse, Solution,
+};
+use std::{
+    io::BufRead,
+    ops::{Add, AddAssign, Mul},
+    str::FromStr,
+};
+
+pub enum Command {
+    North(i64),
+    South(i64),
+    East(i64),
+    West(i64),
+    Left(i64),
+    Right(i64),
+    Forward(i64),
+}
+
+impl FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chr = match s.chars().next() {
+            Some(chr) => chr,
+            None => return Err(ParseError::new(s, 1, "expected a command")),
+        };
+        let val = parse::field(s, &s[chr.len_utf8()..], "invalid value")?;
+        match (chr, val) {
+            ('N', val) => Ok(Command::North(val)),
+            ('S', val) => Ok(Command::South(val)),
+            ('E', val) => Ok(Command::East(val)),
+            ('W', val) => Ok(Command::West(val)),
+            ('L', val) if val % 90 == 0 => Ok(Command::Left(val)),
+            ('R', val) if val % 90 == 0 => Ok(Command::Right(val)),
+            ('L', _) | ('R', _) => Err(ParseError::new(
+                s,
+                2,
+                "turns must be a multiple of 90 degrees",
+            )),
+            ('F', val) => Ok(Command::Forward(val)),
+            _ => Err(ParseError::new(
+                s,
+                1,
+                "expected one of `N`, `S`, `E`, `W`, `L`, `R` or `F`",
+            )),
+        }
+    }
+}
+
+#[derive(Default)]
+enum Heading {
+    North,
+    South,
+    #[default]
+    East,
+    West,
+}
+
+impl Heading {
+    fn turn_left(&self, degrees: i64) -> Self {
+        self.rotate(degrees)
+    }
+
+    fn turn_right(&self, degrees: i64) -> Self {
+        self.rotate(-degrees)
+    }
+
+    fn rotate(&self, degrees: i64) -> Self {
+        Self::from_degrees(self.to_degrees() + degrees)
+    }
+
+    fn from_degrees(degrees: i64) -> Self {
+        match degrees.rem_euclid(360) {
+            0 => Heading::East,
+            90 => Heading::North,
+            180 => Heading::West,
+            270 => Heading::South,
+            _ => panic!("Don't know how to handle {} degrees", degrees),
+        }
+    }
+
+    fn to_degrees(&self) -> i64 {
+        match self {
+            Heading::East => 0,
+            Heading::North => 90,
+            Heading::West => 180,
+            Heading::South => 270,
+        }
+    }
+
+    fn to_vec(&self) -> Vec2 {
+        match self {
+            Heading::East => Vec2::new(1, 0),
+            Heading::North => Vec2::new(0, 1),
+            Heading::West => Vec2::new(-1, 0),
+            Heading::South => Vec2::new(0, -1),
+        }
+    }
+}
+
+#[derive(Default, Copy, Clone)]
+struct Vec2 {
+    x: i64,
+    y: i64,
+}
+
+impl Vec2 {
+    fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Vec2) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl Mul<Vec2> for i64 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: Vec2) -> Self::Output {
+        Vec2::new(rhs.x * self, rhs.y * self)
+    }
+}
+
+struct Ship {
+    position: Vec2,
+    heading: Heading,
+    waypoint: Vec2,
+}
+
+impl Ship {
+    fn execute_part_1(&mut self, command: &Command) {
+        match command {
+            Command::North(val) => self.position += *val * Heading::North.to_vec(),
+            Command::South(val) => self.position += *val * Heading::South.to_vec(),
+            Command::East(val) => self.position += *val * Heading::East.to_vec(),
+            Command::West(val) => self.position += *val * Heading::West.to_vec(),
+            Command::Left(val) => self.heading = self.heading.turn_left(*val),
+            Command::Right(val) => self.heading = self.heading.turn_right(*val),
+            Command::Forward(val) => self.position += *val * self.heading.to_vec(),
+        }
+    }
+
+    fn execute_part_2(&mut self, command: &Command) {
+        match command {
+            Command::North(val) => self.waypoint += *val * Heading::North.to_vec(),
+            Command::South(val) => self.waypoint += *val * Heading::South.to_vec(),
+            Command::East(val) => self.waypoint += *val * Heading::East.to_vec(),
+            Command::West(val) => self.waypoint += *val * Heading::West.to_vec(),
+            Command::Left(val) => {
+                let rotated_x_component = self.waypoint.x * Heading::East.turn_left(*val).to_vec();
+                let rotated_y_component = self.waypoint.y * Heading::North.turn_left(*val).to_vec();
+                self.waypoint = rotated_x_component + rotated_y_component;
+            }
+            Command::Right(val) => {
+                let rotated_x_component = self.waypoint.x * Heading::East.turn_right(*val).to_vec();
+                let rotated_y_component =
+                    self.waypoint.y * Heading::North.turn_right(*val).to_vec();
+                self.waypoint = rotated_x_component + rotated_y_component;
+            }
+            Command::Forward(val) => self.position += *val * self.waypoint,
+        }
+    }
+
+    fn manhattan_distance(&self) -> i64 {
+        self.position.x.abs() + self.position.y.abs()
+    }
+}
+
+impl Default for Ship {
+    fn default() -> Self {
+        Self {
+            position: Vec2::new(0, 0),
+            heading: Heading::default(),
+            waypoint: Vec2::new(10, 1),
+        }
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Command>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input, str::parse)?)
+    }
+
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse::read_lines(reader, str::parse)
+    }
+
+    fn part1(commands: &Self::Input) -> Option<i64> {
+        let mut ship: Ship = Default::default();
+        for command in commands {
+            ship.execute_part_1(command);
+        }
+        Some(ship.manhattan_distance())
+    }
+
+    fn part2(commands: &Self::Input) -> Option<i64> {
+        let mut ship: Ship = Default::default();
+        for command in commands {
+            ship.execute_part_2(command);
+        }
+        Some(ship.manhattan_distance())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+F10
+N3
+F7
+R90
+F11
+";
+
+    #[test]
+    fn example() {
+        let commands = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&commands), Some(25));
+        assert_eq!(Day12::part2(&commands), Some(286));
+    }
+
+    #[test]
+    fn input() {
+        let commands = Day12::parse(include_str!("../../data/day_12.txt")).unwrap();
+        assert_eq!(Day12::part1(&commands), Some(2458));
+        assert_eq!(Day12::part2(&commands), Some(145117));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    error::{ParseError, Result},
+    parse, Solution,
+};
+
+pub struct Notes {
+    earliest_possible_departure_time: u64,
+    // Each bus's frequency, or `None` for an "x" entry in the schedule.
+    busses: Vec<Option<u64>>,
+}
+
+fn parse_notes(input: &str) -> Result<Notes, ParseError> {
+    let mut lines = input.lines();
+    let line = lines.next().unwrap_or("");
+    let earliest_possible_departure_time = parse::field(line, line, "invalid departure time")?;
+    let line = match lines.next() {
+        Some(line) => line,
+        None => return Err(ParseError::new("", 1, "expected a bus schedule").on_line(2)),
+    };
+    let busses = line
+        .split(',')
+        .map(|s| match s {
+            "x" => Ok(None),
+            s => match parse::field(line, s, "invalid bus")? {
+                0 => Err(ParseError::new(
+                    line,
+                    parse::column(line, s),
+                    "bus 0 never departs",
+                )),
+                frequency => Ok(Some(frequency)),
+            },
+        })
+        .collect::<Result<Vec<Option<u64>>, ParseError>>()
+        .map_err(|err| err.on_line(2))?;
+    if busses.iter().all(Option::is_none) {
+        return Err(ParseError::new(line, 1, "the schedule has no buses").on_line(2));
+    }
+
+    Ok(Notes {
+        earliest_possible_departure_time,
+        busses,
+    })
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Notes;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_notes(input)?)
+    }
+
+    fn part1(notes: &Notes) -> Option<u64> {
+        part1(notes)
+    }
+
+    fn part2(notes: &Notes) -> Option<u64> {
+        part2(notes)
+    }
+}
+
+fn part1(notes: &Notes) -> Option<u64> {
+    let earliest_possible_departure_time = notes.earliest_possible_departure_time;
+    let busses = notes.busses.iter().flatten().collect::<Vec<&u64>>();
+    if busses.is_empty() {
+        return None;
+    }
+
+    for time in earliest_possible_departure_time.. {
+        for bus in &busses {
+            if time % *bus == 0 {
+                return Some((time - earliest_possible_departure_time) * *bus);
+            }
+        }
+    }
+    None
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        b => gcd(b, a % b),
+    }
+}
+
+// The earliest time at which each bus leaves as many minutes after it as
+// its place in the schedule, if there is one.  Buses whose frequencies
+// share a factor may never line up.
+fn part2(notes: &Notes) -> Option<u64> {
+    let mut busses = notes
+        .busses
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, bus)| bus.map(|frequency| (idx as u64, frequency)))
+        .collect::<Vec<(u64, u64)>>();
+
+    if busses.is_empty() {
+        return None;
+    }
+
+    let mut modulus = 1u64;
+    let mut current_time = 0u64;
+    busses.sort_by_key(|(_, frequency)| std::cmp::Reverse(*frequency));
+
+    for (offset, frequency) in busses {
+        let target_value_modulo_frequency = (frequency - (offset % frequency)) % frequency;
+        // The candidates modulo `frequency` repeat after `frequency / gcd`
+        // of them, so if none of those fits, none ever will.
+        let common_factor = gcd(modulus, frequency);
+        current_time = (current_time..)
+            .step_by(modulus as usize)
+            .take((frequency / common_factor) as usize)
+            .find(|candidate_time| candidate_time % frequency == target_value_modulo_frequency)?;
+        modulus = modulus / common_factor * frequency;
+    }
+
+    Some(current_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+939
+7,13,x,x,59,x,31,19
+";
+
+    #[test]
+    fn example() {
+        let notes = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part1(&notes), Some(295));
+        assert_eq!(Day13::part2(&notes), Some(1068781));
+    }
+
+    #[test]
+    fn sieve() {
+        let schedules = [
+            ("17,x,13,19", 3417),
+            ("67,7,59,61", 754018),
+            ("67,x,7,59,61", 779210),
+            ("67,7,x,59,61", 1261476),
+            ("1789,37,47,1889", 1202161486),
+        ];
+        for (schedule, timestamp) in schedules.iter() {
+            let notes = Day13::parse(&format!("0\n{}\n", schedule)).unwrap();
+            assert_eq!(Day13::part2(&notes), Some(*timestamp));
+        }
+    }
+
+    #[test]
+    fn no_answer() {
+        // No buses at all, which the parser rejects, and buses which can
+        // never leave a minute apart.
+        let notes = Notes {
+            earliest_possible_departure_time: 939,
+            busses: vec![None, None],
+        };
+        assert_eq!(Day13::part1(&notes), None);
+        assert_eq!(Day13::part2(&notes), None);
+        let notes = Day13::parse("0\n4,2\n").unwrap();
+        assert_eq!(Day13::part1(&notes), Some(0));
+        assert_eq!(Day13::part2(&notes), None);
+        // Sharing a factor is fine as long as the offsets agree.
+        let notes = Day13::parse("0\n4,x,2\n").unwrap();
+        assert_eq!(Day13::part2(&notes), Some(0));
+        let notes = Day13::parse("0\n6,x,4\n").unwrap();
+        assert_eq!(Day13::part2(&notes), Some(6));
+    }
+
+    #[test]
+    fn no_buses() {
+        let err = match parse_notes("939\nx,x\n") {
+            Err(err) => err,
+            Ok(_) => panic!("expected a parse error"),
+        };
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "the schedule has no buses");
+    }
+
+    #[test]
+    fn input() {
+        let notes = Day13::parse(include_str!("../../data/day_13.txt")).unwrap();
+        assert_eq!(Day13::part1(&notes), Some(4315));
+        assert_eq!(Day13::part2(&notes), Some(556100168221141));
+    }
+}
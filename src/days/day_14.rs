@@ -1,4 +1,8 @@
-use std::collections::HashMap;
+use crate::{
+    error::{ParseError, Result},
+    Solution,
+};
+use std::{collections::HashMap, str::FromStr};
 
 #[derive(Copy, Clone)]
 enum BitSpec {
@@ -8,7 +12,7 @@ enum BitSpec {
 }
 
 #[derive(Copy, Clone)]
-struct Mask {
+pub struct Mask {
     bits: [BitSpec; 36],
 }
 
@@ -30,10 +34,7 @@ impl Mask {
     fn apply_part_2(&self, val: u64) -> Vec<u64> {
         let mut masked = [BitSpec::X; 36];
 
-        let bit_values = (0..36)
-            .into_iter()
-            .rev()
-            .map(|bit_idx| (val >> bit_idx) & 1);
+        let bit_values = (0..36).rev().map(|bit_idx| (val >> bit_idx) & 1);
         let bit_specs = self.bits.iter();
 
         for (idx, (bit_value, bit_spec)) in bit_values.zip(bit_specs).enumerate() {
@@ -82,22 +83,28 @@ impl Mask {
     }
 }
 
-impl From<&str> for Mask {
-    fn from(s: &str) -> Self {
+impl FromStr for Mask {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut result = Self {
             bits: [BitSpec::X; 36],
         };
 
+        if s.chars().count() != 36 {
+            return Err(ParseError::new(s, 1, "expected 36 bits"));
+        }
+
         for (idx, chr) in s.chars().enumerate() {
             match chr {
                 'X' => {}
                 '0' => result.bits[idx] = BitSpec::Zero,
                 '1' => result.bits[idx] = BitSpec::One,
-                _ => panic!("Unexpected character in mask {}", chr),
+                _ => return Err(ParseError::new(s, idx + 1, "expected `X`, `0` or `1`")),
             }
         }
 
-        result
+        Ok(result)
     }
 }
 
@@ -116,11 +123,12 @@ impl std::fmt::Debug for Mask {
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Mask(Mask),
     WriteMem(u64, u64),
 }
 
+// The parser makes sure a program sets a mask before it writes to memory.
 #[derive(Default)]
 struct State {
     mask: Option<Mask>,
@@ -132,8 +140,9 @@ impl State {
         match instruction {
             Instruction::Mask(m) => self.mask = Some(*m),
             Instruction::WriteMem(addr, val) => {
-                self.mem
-                    .insert(*addr, self.mask.unwrap().apply_part_1(*val));
+                if let Some(mask) = &self.mask {
+                    self.mem.insert(*addr, mask.apply_part_1(*val));
+                }
             }
         }
     }
@@ -142,7 +151,7 @@ impl State {
         match instruction {
             Instruction::Mask(m) => self.mask = Some(*m),
             Instruction::WriteMem(addr, val) => {
-                for addr_instance in self.mask.unwrap().apply_part_2(*addr) {
+                for addr_instance in self.mask.iter().flat_map(|mask| mask.apply_part_2(*addr)) {
                     self.mem.insert(addr_instance, *val);
                 }
             }
@@ -154,30 +163,35 @@ impl State {
     }
 }
 
-fn parse_program(input: &str) -> Vec<Instruction> {
+fn parse_program(input: &str) -> Result<Vec<Instruction>, ParseError> {
     peg::parser! {
         pub(crate) grammar parser() for str {
+            // A write means nothing until there is a mask to apply.
             pub(crate) rule root() -> Vec<Instruction>
-                = i:instruction()+ ![_] { i }
+                = m:mask() i:instruction()* ![_] {
+                    std::iter::once(m).chain(i).collect()
+                }
 
             rule instruction() -> Instruction
                 = m:mask() { m }
                 / w:mem_write() { w }
 
             rule mask() -> Instruction
-                = "mask = " m:$(['X' | '0' | '1']+) newline()? { Instruction::Mask(m.into()) }
+                = "mask = " m:$(['X' | '0' | '1']+) newline()? {?
+                    m.parse().map(Instruction::Mask).or(Err("a 36 bit mask"))
+                }
 
             rule mem_write() -> Instruction
                 = "mem[" a:number() "] = " v:number() newline()? { Instruction::WriteMem(a, v) }
 
             rule number() -> u64
-                = n:$(['0'..='9']+) { n.parse().unwrap() }
+                = n:$(['0'..='9']+) {? n.parse().or(Err("a number")) }
 
             rule newline()
                 = ['\n' | '\r']+
         }
     }
-    parser::root(input).unwrap()
+    parser::root(input).map_err(|err| ParseError::from_peg(input, err))
 }
 
 fn part1(program: &[Instruction]) -> u64 {
@@ -196,10 +210,78 @@ fn part2(program: &[Instruction]) -> u64 {
     state.sum()
 }
 
-fn main() {
-    let input = include_str!("../../data/day_14.txt");
-    let program = parse_program(input);
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Instruction>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_program(input)?)
+    }
+
+    fn part1(program: &Self::Input) -> Option<u64> {
+        Some(part1(program))
+    }
+
+    fn part2(program: &Self::Input) -> Option<u64> {
+        Some(part2(program))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0
+";
+
+    const FLOATING_EXAMPLE: &str = "\
+mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(Day14::part1(&Day14::parse(EXAMPLE).unwrap()), Some(165));
+        assert_eq!(
+            Day14::part2(&Day14::parse(FLOATING_EXAMPLE).unwrap()),
+            Some(208)
+        );
+    }
 
-    println!("{}", part1(&program));
-    println!("{}", part2(&program));
+    #[test]
+    fn write_before_mask() {
+        let err = parse_program("mem[8] = 11\nmask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\n")
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.message, "expected \"mask = \"");
+    }
+
+    #[test]
+    fn variations() {
+        let mask: Mask = "000000000000000000000000000000X1001X".parse().unwrap();
+        let mut addresses = mask.apply_part_2(42);
+        addresses.sort_unstable();
+        assert_eq!(addresses, vec![26, 27, 58, 59]);
+
+        let mask: Mask = "00000000000000000000000000000000X0XX".parse().unwrap();
+        let mut addresses = mask.apply_part_2(26);
+        addresses.sort_unstable();
+        assert_eq!(addresses, vec![16, 17, 18, 19, 24, 25, 26, 27]);
+    }
+
+    #[test]
+    fn input() {
+        let program = Day14::parse(include_str!("../../data/day_14.txt")).unwrap();
+        assert_eq!(Day14::part1(&program), Some(9628746976360));
+        assert_eq!(Day14::part2(&program), Some(4574598714592));
+    }
 }
@@ -0,0 +1,121 @@
+use crate::{
+    error::{ParseError, Result},
+    parse, Solution,
+};
+use std::{io::BufRead, str::FromStr};
+
+#[derive(Debug)]
+pub struct PasswordEntry {
+    a: usize,
+    b: usize,
+    character: char,
+    password: String,
+}
+
+impl PasswordEntry {
+    fn is_valid_1(&self) -> bool {
+        let char_count = &self
+            .password
+            .chars()
+            .filter(|c| c == &self.character)
+            .count();
+        char_count >= &self.a && char_count <= &self.b
+    }
+
+    fn is_valid_2(&self) -> bool {
+        let char_a = &self.password.chars().nth(&self.a - 1);
+        let char_b = &self.password.chars().nth(&self.b - 1);
+        (char_a == &Some(self.character) || char_b == &Some(self.character)) && char_a != char_b
+    }
+}
+
+impl FromStr for PasswordEntry {
+    type Err = ParseError;
+
+    // Entries look like `1-3 a: abcde`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let components: Vec<&str> = s.split(' ').collect();
+        if components.len() != 3 {
+            return Err(ParseError::new(
+                s,
+                1,
+                "expected a policy, a character and a password",
+            ));
+        }
+        let a_b: Vec<&str> = components[0].split('-').collect();
+        if a_b.len() != 2 {
+            return Err(ParseError::new(s, 1, "expected a range such as `1-3`"));
+        }
+        let a = parse::field(s, a_b[0], "invalid position")?;
+        let b = parse::field(s, a_b[1], "invalid position")?;
+        if a == 0 || b == 0 {
+            return Err(ParseError::new(s, 1, "positions start at 1"));
+        }
+        let character = match components[1].strip_suffix(':') {
+            Some(c) if c.chars().count() == 1 => c.chars().next().unwrap(),
+            _ => {
+                return Err(ParseError::new(
+                    s,
+                    parse::column(s, components[1]),
+                    "expected a single character followed by `:`",
+                ))
+            }
+        };
+        let password = components[2].to_string();
+        Ok(PasswordEntry {
+            a,
+            b,
+            character,
+            password,
+        })
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<PasswordEntry>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input, str::parse)?)
+    }
+
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse::read_lines(reader, str::parse)
+    }
+
+    fn part1(passwords: &Self::Input) -> Option<usize> {
+        Some(passwords.iter().filter(|p| p.is_valid_1()).count())
+    }
+
+    fn part2(passwords: &Self::Input) -> Option<usize> {
+        Some(passwords.iter().filter(|p| p.is_valid_2()).count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc
+";
+
+    #[test]
+    fn example() {
+        let passwords = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part1(&passwords), Some(2));
+        assert_eq!(Day2::part2(&passwords), Some(1));
+    }
+
+    #[test]
+    fn input() {
+        let passwords = Day2::parse(include_str!("../../data/day_2.txt")).unwrap();
+        assert_eq!(Day2::part1(&passwords), Some(465));
+        assert_eq!(Day2::part2(&passwords), Some(294));
+    }
+}
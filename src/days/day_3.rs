@@ -0,0 +1,112 @@
+use crate::{
+    error::{ParseError, Result},
+    grid::{Grid, Position},
+    Solution,
+};
+use std::str::FromStr;
+
+pub struct Map {
+    trees: Grid<bool>,
+}
+
+impl Map {
+    fn has_tree(&self, x: usize, y: usize) -> bool {
+        *self
+            .trees
+            .get_wrapping(Position::new(x as isize, y as isize))
+    }
+
+    fn count_trees(&self, dx: usize, dy: usize) -> usize {
+        let mut x = 0;
+        let mut y = 0;
+        let mut tree_count = 0;
+
+        loop {
+            if y >= self.trees.height() {
+                break;
+            }
+            if self.has_tree(x, y) {
+                tree_count += 1
+            }
+            x += dx;
+            y += dy;
+        }
+
+        tree_count
+    }
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trees = Grid::parse(
+            s,
+            |c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            },
+            "expected `#` or `.`",
+        )?;
+        Ok(Map { trees })
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Map;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(map: &Map) -> Option<usize> {
+        Some(map.count_trees(3, 1))
+    }
+
+    fn part2(map: &Map) -> Option<usize> {
+        let slopes = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        Some(
+            slopes
+                .into_iter()
+                .fold(1, |acc, (dx, dy)| acc * map.count_trees(dx, dy)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#
+";
+
+    #[test]
+    fn example() {
+        let map = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part1(&map), Some(7));
+        assert_eq!(Day3::part2(&map), Some(336));
+    }
+
+    #[test]
+    fn input() {
+        let map = Day3::parse(include_str!("../../data/day_3.txt")).unwrap();
+        assert_eq!(Day3::part1(&map), Some(268));
+        assert_eq!(Day3::part2(&map), Some(3093068400));
+    }
+}
@@ -1,5 +1,8 @@
+use crate::{
+    error::{ParseError, Result},
+    Solution,
+};
 use std::collections::HashSet;
-use std::fs;
 
 enum ParserState {
     Key,
@@ -7,7 +10,7 @@ enum ParserState {
 }
 
 #[derive(Debug)]
-struct Passport {
+pub struct Passport {
     byr: Option<String>,
     iyr: Option<String>,
     eyr: Option<String>,
@@ -32,7 +35,7 @@ impl Passport {
         }
     }
 
-    fn with_key_val(mut self, key: &str, val: &str) -> Passport {
+    fn with_key_val(mut self, key: &str, val: &str) -> Option<Passport> {
         let val = Some(val.to_string());
         match key {
             "byr" => self.byr = val,
@@ -43,9 +46,9 @@ impl Passport {
             "ecl" => self.ecl = val,
             "pid" => self.pid = val,
             "cid" => self.cid = val,
-            s => panic!("Unexpected key: {}", s),
+            _ => return None,
         }
-        self
+        Some(self)
     }
 
     fn required_fields_present(&self) -> bool {
@@ -97,13 +100,13 @@ impl Passport {
                 } else if &hgt[hgt.len() - 2..] == "cm" {
                     let height = hgt[..hgt.len() - 2].parse::<i32>();
                     match height {
-                        Ok(height) => 150 <= height && height <= 193,
+                        Ok(height) => (150..=193).contains(&height),
                         _ => false,
                     }
                 } else if &hgt[hgt.len() - 2..] == "in" {
                     let height = hgt[..hgt.len() - 2].parse::<i32>();
                     match height {
-                        Ok(height) => 59 <= height && height <= 76,
+                        Ok(height) => (59..=76).contains(&height),
                         _ => false,
                     }
                 } else {
@@ -188,14 +191,27 @@ impl Passport {
     }
 }
 
-fn load_passports() -> Vec<Passport> {
-    let contents = fs::read_to_string("data/day_4.txt").expect("File must be present");
+fn parse_passports(contents: &str) -> Result<Vec<Passport>, ParseError> {
     let mut state = ParserState::Key;
     let mut passports: Vec<Passport> = Vec::new();
     let mut buff = Vec::new();
     let mut key: String = String::new();
     let mut passport = Passport::new();
-    for chr in contents.chars() {
+    let mut line = 1;
+    let mut column = 1;
+    let mut key_column = 1;
+    let error = |line: usize, column: usize, message: String| {
+        let text = contents.lines().nth(line - 1).unwrap_or("");
+        ParseError::new(text, column, message).on_line(line)
+    };
+    // A newline terminates the final field, so supply one if the input
+    // lacks it.
+    let terminator = if contents.ends_with('\n') {
+        None
+    } else {
+        Some('\n')
+    };
+    for chr in contents.chars().chain(terminator) {
         match &state {
             ParserState::Key => match chr {
                 ':' => {
@@ -203,11 +219,17 @@ fn load_passports() -> Vec<Passport> {
                     buff.clear();
                     state = ParserState::Val;
                 }
+                ' ' | '\n' if !buff.is_empty() => {
+                    return Err(error(line, column, "expected `:` after key".into()));
+                }
                 '\n' => {
                     passports.push(passport);
                     passport = Passport::new();
                 }
                 _ => {
+                    if buff.is_empty() {
+                        key_column = column;
+                    }
                     buff.push(chr);
                 }
             },
@@ -215,7 +237,13 @@ fn load_passports() -> Vec<Passport> {
                 ' ' | '\n' => {
                     let val: String = buff.iter().collect();
                     buff.clear();
-                    passport = passport.with_key_val(&key, &val);
+                    passport = match passport.with_key_val(&key, &val) {
+                        Some(passport) => passport,
+                        None => {
+                            let message = format!("unexpected key `{}`", key);
+                            return Err(error(line, key_column, message));
+                        }
+                    };
                     state = ParserState::Key;
                 }
                 _ => {
@@ -223,18 +251,109 @@ fn load_passports() -> Vec<Passport> {
                 }
             },
         }
+
+        if chr == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
     }
     passports.push(passport);
-    passports
+    Ok(passports)
 }
 
-fn main() {
-    let passports = load_passports();
-    let part_1_count = passports
-        .iter()
-        .filter(|p| p.required_fields_present())
-        .count();
-    let part_2_count = passports.iter().filter(|p| p.is_valid()).count();
-    println!("{}", part_1_count);
-    println!("{}", part_2_count);
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Passport>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_passports(input)?)
+    }
+
+    fn part1(passports: &Self::Input) -> Option<usize> {
+        Some(
+            passports
+                .iter()
+                .filter(|p| p.required_fields_present())
+                .count(),
+        )
+    }
+
+    fn part2(passports: &Self::Input) -> Option<usize> {
+        Some(passports.iter().filter(|p| p.is_valid()).count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in
+";
+
+    const INVALID_PASSPORTS: &str = "\
+eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007
+";
+
+    const VALID_PASSPORTS: &str = "\
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
+";
+
+    #[test]
+    fn example() {
+        let passports = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part1(&passports), Some(2));
+        assert_eq!(
+            Day4::part2(&Day4::parse(INVALID_PASSPORTS).unwrap()),
+            Some(0)
+        );
+        assert_eq!(Day4::part2(&Day4::parse(VALID_PASSPORTS).unwrap()), Some(4));
+    }
+
+    #[test]
+    fn input() {
+        let passports = Day4::parse(include_str!("../../data/day_4.txt")).unwrap();
+        assert_eq!(Day4::part1(&passports), Some(235));
+        assert_eq!(Day4::part2(&passports), Some(194));
+    }
 }
@@ -0,0 +1,102 @@
+use crate::{
+    error::{ParseError, Result},
+    parse, Solution,
+};
+
+#[derive(Debug)]
+pub struct Seat {
+    row: usize,
+    col: usize,
+}
+
+impl Seat {
+    fn new(row: usize, col: usize) -> Seat {
+        Seat { row, col }
+    }
+
+    fn id(&self) -> usize {
+        self.row * 8 + self.col
+    }
+}
+
+// Boarding passes look like `FBFBBFFRLR`: seven characters for the row
+// followed by three for the column.
+fn parse_seat(s: &str) -> Result<Seat, ParseError> {
+    if s.chars().count() != 10 {
+        return Err(ParseError::new(s, 1, "expected 10 characters"));
+    }
+    let mut row = 0;
+    let mut col = 0;
+
+    for (i, chr) in s.chars().enumerate() {
+        match (i, chr) {
+            (0..=6, 'F') | (7..=9, 'L') => {}
+            (0..=6, 'B') => row += usize::pow(2, 6 - i as u32),
+            (7..=9, 'R') => col += usize::pow(2, 9 - i as u32),
+            (0..=6, _) => return Err(ParseError::new(s, i + 1, "expected `F` or `B`")),
+            _ => return Err(ParseError::new(s, i + 1, "expected `L` or `R`")),
+        }
+    }
+
+    Ok(Seat::new(row, col))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Seat>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input, parse_seat)?)
+    }
+
+    fn part1(seats: &Self::Input) -> Option<usize> {
+        seats.iter().map(|s| s.id()).max()
+    }
+
+    fn part2(seats: &Self::Input) -> Option<usize> {
+        let mut previous_seat_id: Option<usize> = None;
+        let mut seat_ids: Vec<usize> = seats.iter().map(|s| s.id()).collect();
+        seat_ids.sort_unstable();
+        for seat_id in seat_ids {
+            if let Some(previous_seat_id) = previous_seat_id {
+                if seat_id - previous_seat_id > 1 {
+                    return Some(seat_id - 1);
+                }
+            }
+            previous_seat_id = Some(seat_id)
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let seat_ids = ["FBFBBFFRLR", "BFFFBBFRRR", "FFFBBBFRRR", "BBFFBBFRLL"]
+            .iter()
+            .map(|s| parse_seat(s).unwrap().id())
+            .collect::<Vec<usize>>();
+        assert_eq!(seat_ids, vec![357, 567, 119, 820]);
+    }
+
+    #[test]
+    fn no_seats() {
+        let seats = Day5::parse("\n").unwrap();
+        assert_eq!(Day5::part1(&seats), None);
+        assert_eq!(Day5::part2(&seats), None);
+    }
+
+    #[test]
+    fn input() {
+        let seats = Day5::parse(include_str!("../../data/day_5.txt")).unwrap();
+        assert_eq!(Day5::part1(&seats), Some(892));
+        assert_eq!(Day5::part2(&seats), Some(625));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{
+    error::{ParseError, Result},
+    Solution,
+};
+use std::collections::HashSet;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    // Each group is the list of answer lines given by its members.
+    type Input = Vec<Vec<String>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut groups = vec![];
+        let mut group: Vec<String> = vec![];
+        for (idx, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                if !group.is_empty() {
+                    groups.push(std::mem::take(&mut group));
+                }
+                continue;
+            }
+            if let Some(column) = line.chars().position(|c| !c.is_ascii_lowercase()) {
+                let err = ParseError::new(line, column + 1, "expected a question from `a` to `z`");
+                return Err(err.on_line(idx + 1).into());
+            }
+            group.push(line.into());
+        }
+        if !group.is_empty() {
+            groups.push(group);
+        }
+        Ok(groups)
+    }
+
+    fn part1(groups: &Self::Input) -> Option<usize> {
+        let answers_sets = groups.iter().map(|group| {
+            let mut answers_set = HashSet::new();
+            for answers in group {
+                for answer in answers.chars() {
+                    answers_set.insert(answer);
+                }
+            }
+            answers_set
+        });
+        Some(answers_sets.map(|set| set.len()).sum())
+    }
+
+    fn part2(groups: &Self::Input) -> Option<usize> {
+        let answers_sets = groups.iter().map(|group| {
+            let mut all_answers_set: Option<HashSet<char>> = None;
+            for answers in group {
+                let mut answers_set = HashSet::new();
+                for answer in answers.chars() {
+                    answers_set.insert(answer);
+                }
+                match all_answers_set {
+                    Some(set) => {
+                        all_answers_set = Some(set.intersection(&answers_set).cloned().collect());
+                    }
+                    None => {
+                        all_answers_set = Some(answers_set);
+                    }
+                }
+            }
+            all_answers_set.unwrap()
+        });
+        Some(answers_sets.map(|set| set.len()).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b
+";
+
+    #[test]
+    fn example() {
+        let groups = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part1(&groups), Some(11));
+        assert_eq!(Day6::part2(&groups), Some(6));
+    }
+
+    #[test]
+    fn input() {
+        let groups = Day6::parse(include_str!("../../data/day_6.txt")).unwrap();
+        assert_eq!(Day6::part1(&groups), Some(6799));
+        assert_eq!(Day6::part2(&groups), Some(3354));
+    }
+}
@@ -0,0 +1,424 @@
+use crate::{error::Result, Solution};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+mod export;
+mod query;
+mod validate;
+
+pub use export::{export, Export, Format};
+pub use query::{Query, HELP};
+pub use validate::validate;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BagType(String);
+
+impl fmt::Display for BagType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for BagType {
+    fn from(name: &str) -> Self {
+        BagType(name.into())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct BagMember {
+    quantity: u64,
+    bag: BagType,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct BagRule {
+    container: BagType,
+    members: Vec<BagMember>,
+}
+
+// Bags which each contain the next, the last containing the first, so
+// that each would have to contain itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle(pub Vec<BagType>);
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bags contain themselves: ")?;
+        for bag in &self.0 {
+            write!(f, "{} -> ", bag)?;
+        }
+        write!(f, "{}", self.0[0])
+    }
+}
+
+pub struct BagGraph<'a> {
+    rules: &'a [BagRule],
+    bag_to_rule: HashMap<&'a BagType, &'a BagRule>,
+    // The containers with a rule naming each bag as a member.
+    contained_by: HashMap<&'a BagType, Vec<&'a BagType>>,
+    // Every bag, each after all of its members.
+    bottom_up: Vec<&'a BagType>,
+    // How many bags each bag holds in all, worked out once up front.
+    totals: HashMap<&'a BagType, u64>,
+}
+
+impl<'a> BagGraph<'a> {
+    // The graph of `rules`, which must not have any bag contain itself,
+    // directly or otherwise, so that walking down from any bag ends.
+    pub fn new(rules: &'a [BagRule]) -> Result<BagGraph<'a>, Cycle> {
+        let mut bag_to_rule = HashMap::new();
+        let mut contained_by: HashMap<_, Vec<_>> = HashMap::new();
+
+        for rule in rules {
+            bag_to_rule.insert(&rule.container, rule);
+            for member in &rule.members {
+                contained_by
+                    .entry(&member.bag)
+                    .or_default()
+                    .push(&rule.container);
+            }
+        }
+
+        let mut bag_graph = BagGraph {
+            rules,
+            bag_to_rule,
+            contained_by,
+            bottom_up: vec![],
+            totals: HashMap::new(),
+        };
+        bag_graph.bottom_up = bag_graph
+            .order(rules)
+            .map_err(|cycle| Cycle(cycle.into_iter().cloned().collect()))?;
+        for &bag in &bag_graph.bottom_up {
+            let total = bag_graph
+                .members(bag)
+                .iter()
+                .map(|m| m.quantity * (1 + bag_graph.totals[&m.bag]))
+                .sum();
+            bag_graph.totals.insert(bag, total);
+        }
+        Ok(bag_graph)
+    }
+
+    // The graph's own copy of `bag`, if any rule names it.
+    pub fn find(&self, bag: &BagType) -> Option<&'a BagType> {
+        match self.bag_to_rule.get_key_value(bag) {
+            Some((&bag, _)) => Some(bag),
+            None => self.contained_by.get_key_value(bag).map(|(&bag, _)| bag),
+        }
+    }
+
+    // The bags directly inside `bag`.
+    pub fn members(&self, bag: &BagType) -> &'a [BagMember] {
+        self.bag_to_rule
+            .get(bag)
+            .map_or(&[], |rule| rule.members.as_slice())
+    }
+
+    // Every bag, each after all of its members, or the first cycle found
+    // if there is no such order.  Walks down from each container in turn,
+    // keeping the path from where the walk started; a member already on
+    // the path closes a cycle.
+    fn order(&self, rules: &'a [BagRule]) -> Result<Vec<&'a BagType>, Vec<&'a BagType>> {
+        let mut finished = HashSet::new();
+        let mut order = vec![];
+
+        for rule in rules {
+            let mut path: Vec<(&BagType, usize)> = vec![(&rule.container, 0)];
+            let mut on_path: HashSet<&BagType> = HashSet::new();
+            on_path.insert(&rule.container);
+
+            while let Some(&(bag, next)) = path.last() {
+                if finished.contains(bag) {
+                    on_path.remove(bag);
+                    path.pop();
+                    continue;
+                }
+                match self.members(bag).get(next) {
+                    Some(member) => {
+                        path.last_mut().unwrap().1 += 1;
+                        if on_path.contains(&member.bag) {
+                            let start = path.iter().position(|&(b, _)| b == &member.bag).unwrap();
+                            return Err(path[start..].iter().map(|&(b, _)| b).collect());
+                        }
+                        on_path.insert(&member.bag);
+                        path.push((&member.bag, 0));
+                    }
+                    None => {
+                        finished.insert(bag);
+                        order.push(bag);
+                        on_path.remove(bag);
+                        path.pop();
+                    }
+                }
+            }
+        }
+
+        Ok(order)
+    }
+
+    // Every bag named by a rule: the containers in the order of their rules
+    // and then any members without rules of their own.
+    pub fn bags(&self) -> Vec<&'a BagType> {
+        let mut seen = HashSet::new();
+        let containers = self.rules.iter().map(|rule| &rule.container);
+        let members = self
+            .rules
+            .iter()
+            .flat_map(|rule| rule.members.iter().map(|m| &m.bag));
+        containers
+            .chain(members)
+            .filter(|bag| seen.insert(*bag))
+            .collect()
+    }
+
+    // Every bag which holds `bag`, however deep down.
+    pub fn ancestors(&self, bag: &BagType) -> HashSet<&'a BagType> {
+        let mut ancestors = HashSet::new();
+        let mut bags_to_try = vec![bag];
+
+        while let Some(bag_to_try) = bags_to_try.pop() {
+            for &container in self.contained_by.get(bag_to_try).into_iter().flatten() {
+                if ancestors.insert(container) {
+                    bags_to_try.push(container);
+                }
+            }
+        }
+
+        ancestors
+    }
+
+    // Every bag inside `bag`, however deep down.
+    pub fn descendants(&self, bag: &BagType) -> HashSet<&'a BagType> {
+        let mut descendants = HashSet::new();
+        let mut bags_to_try = vec![bag];
+
+        while let Some(bag_to_try) = bags_to_try.pop() {
+            for member in self.members(bag_to_try) {
+                if descendants.insert(&member.bag) {
+                    bags_to_try.push(&member.bag);
+                }
+            }
+        }
+
+        descendants
+    }
+
+    // The number of bags inside `bag`, however deep down.
+    pub fn total_contents(&self, bag: &BagType) -> u64 {
+        self.totals.get(bag).copied().unwrap_or(0)
+    }
+
+    // `can_contain` and `count_recursive_members` answer the same questions
+    // as `ancestors` and `total_contents` by walking the rules every time,
+    // and are kept to check them against.
+    #[cfg(test)]
+    fn can_contain(&self, src: &BagType, dst: &BagType) -> bool {
+        let mut bags_to_try: Vec<&BagType> = self.members(src).iter().map(|m| &m.bag).collect();
+        let mut tried = HashSet::new();
+
+        while let Some(bag_to_try) = bags_to_try.pop() {
+            if bag_to_try == dst {
+                return true;
+            }
+            if !tried.insert(bag_to_try) {
+                continue;
+            }
+
+            for member in self.members(bag_to_try) {
+                bags_to_try.push(&member.bag)
+            }
+        }
+
+        false
+    }
+
+    #[cfg(test)]
+    fn count_recursive_members(&self, container: &BagType) -> u64 {
+        let members = self.members(container);
+
+        if members.is_empty() {
+            0
+        } else {
+            members
+                .iter()
+                .map(|m| m.quantity * (1 + self.count_recursive_members(&m.bag)))
+                .sum::<u64>()
+        }
+    }
+}
+
+peg::parser! {
+    grammar bag_rule_parser() for str {
+        pub rule bag_rule() -> BagRule
+            = container:bag_type() " bags contain " members:bag_members() { BagRule { container, members } }
+
+        rule bag_type() -> BagType
+            = s:$(t:word() " " t:word()) { BagType(s.into()) }
+
+        rule word() -> &'input str
+            = $(['a'..='z']+)
+
+        rule bag_members() -> Vec<BagMember>
+            = no_members()
+            / b:members_list()
+
+        rule no_members() -> Vec<BagMember>
+            = "no other bags." { Default::default() }
+
+        rule members_list() -> Vec<BagMember>
+            = m:member() ** ", " "."  { m }
+
+        rule member() -> BagMember
+            = n:$(['0'..='9']+) " " t:bag_type() " bag" "s"? {?
+                n.parse().map(|quantity| BagMember { quantity, bag: t }).or(Err("a quantity"))
+            }
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<BagRule>;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        validate(input).map_err(|errors| errors.into_iter().next().unwrap().into())
+    }
+
+    fn part1(bag_rules: &Self::Input) -> Option<usize> {
+        let bag_graph = BagGraph::new(bag_rules).expect("parse rejects cyclic rules");
+        Some(bag_graph.ancestors(&BagType("shiny gold".into())).len())
+    }
+
+    fn part2(bag_rules: &Self::Input) -> Option<u64> {
+        let bag_graph = BagGraph::new(bag_rules).expect("parse rejects cyclic rules");
+        Some(bag_graph.total_contents(&BagType("shiny gold".into())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+";
+
+    const NESTED_EXAMPLE: &str = "\
+shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.
+";
+
+    fn bag(name: &str) -> BagType {
+        BagType(name.into())
+    }
+
+    #[test]
+    fn example() {
+        let bag_rules = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part1(&bag_rules), Some(4));
+        assert_eq!(Day7::part2(&bag_rules), Some(32));
+        assert_eq!(
+            Day7::part2(&Day7::parse(NESTED_EXAMPLE).unwrap()),
+            Some(126)
+        );
+    }
+
+    #[test]
+    fn bag_graph() {
+        let bag_rules = Day7::parse(EXAMPLE).unwrap();
+        let bag_graph = BagGraph::new(&bag_rules).unwrap();
+        assert!(bag_graph.can_contain(&bag("light red"), &bag("faded blue")));
+        assert!(bag_graph.can_contain(&bag("muted yellow"), &bag("shiny gold")));
+        assert!(!bag_graph.can_contain(&bag("shiny gold"), &bag("light red")));
+        assert!(!bag_graph.can_contain(&bag("faded blue"), &bag("faded blue")));
+        assert_eq!(bag_graph.count_recursive_members(&bag("faded blue")), 0);
+        assert_eq!(bag_graph.count_recursive_members(&bag("dark olive")), 7);
+        assert_eq!(bag_graph.count_recursive_members(&bag("vibrant plum")), 11);
+    }
+
+    #[test]
+    fn queries_match_reference() {
+        for input in &[
+            EXAMPLE,
+            NESTED_EXAMPLE,
+            include_str!("../../data/day_7.txt"),
+        ] {
+            let bag_rules = Day7::parse(input).unwrap();
+            let bag_graph = BagGraph::new(&bag_rules).unwrap();
+            // The reference is slow on the full input, so only some of it
+            // is checked.
+            let every = if bag_rules.len() > 100 { 50 } else { 1 };
+            for rule in bag_rules.iter().step_by(every) {
+                let bag = &rule.container;
+                let holders: HashSet<&BagType> = bag_rules
+                    .iter()
+                    .map(|r| &r.container)
+                    .filter(|container| bag_graph.can_contain(container, bag))
+                    .collect();
+                assert_eq!(bag_graph.ancestors(bag), holders);
+                assert_eq!(
+                    bag_graph.total_contents(bag),
+                    bag_graph.count_recursive_members(bag)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        let cyclic = "\
+light red bags contain 1 dark blue bag.
+dark blue bags contain 2 muted green bags, 1 faded blue bag.
+muted green bags contain 3 faded blue bags, 1 light red bag.
+faded blue bags contain no other bags.
+";
+        let bag_rules = validate::check(cyclic).0;
+        let cycle = BagGraph::new(&bag_rules).err().unwrap();
+        assert_eq!(
+            cycle.0,
+            vec![bag("light red"), bag("dark blue"), bag("muted green")]
+        );
+
+        let err = match Day7::parse(cyclic) {
+            Err(crate::error::Error::Parse(err)) => err,
+            _ => panic!("expected a parse error"),
+        };
+        assert_eq!((err.line, err.column), (1, 26));
+        assert_eq!(
+            err.message,
+            "bags contain themselves: light red -> dark blue -> muted green -> light red"
+        );
+
+        let err = match Day7::parse("shiny gold bags contain 2 shiny gold bags.\n") {
+            Err(crate::error::Error::Parse(err)) => err,
+            _ => panic!("expected a parse error"),
+        };
+        assert_eq!(err.column, 27);
+    }
+
+    #[test]
+    fn input() {
+        let bag_rules = Day7::parse(include_str!("../../data/day_7.txt")).unwrap();
+        assert_eq!(Day7::part1(&bag_rules), Some(142));
+        assert_eq!(Day7::part2(&bag_rules), Some(10219));
+    }
+}
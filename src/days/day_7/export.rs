@@ -0,0 +1,222 @@
+use super::{BagGraph, BagType};
+use std::{collections::HashSet, fmt::Write, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Dot,
+    Mermaid,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(Format::Dot),
+            "mermaid" => Ok(Format::Mermaid),
+            _ => Err(format!("expected `dot` or `mermaid`, found `{}`", s)),
+        }
+    }
+}
+
+// What to draw and how.
+#[derive(Debug, Clone)]
+pub struct Export {
+    pub format: Format,
+    // A bag to highlight, along with the bags which hold it and the bags
+    // it holds.
+    pub focus: Option<BagType>,
+    // Leave out every bag which isn't the focus or related to it.  Without
+    // a focus this leaves out every bag.
+    pub related_only: bool,
+}
+
+// How a bag relates to the focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Focus,
+    Ancestor,
+    Descendant,
+    Other,
+}
+
+impl Role {
+    fn name(self) -> &'static str {
+        match self {
+            Role::Focus => "focus",
+            Role::Ancestor => "ancestor",
+            Role::Descendant => "descendant",
+            Role::Other => "other",
+        }
+    }
+
+    fn colour(self) -> &'static str {
+        match self {
+            Role::Focus => "gold",
+            Role::Ancestor => "lightblue",
+            Role::Descendant => "palegreen",
+            Role::Other => "white",
+        }
+    }
+}
+
+// Draw the containment graph with each edge labelled by how many of the
+// member the container holds.
+pub fn export(graph: &BagGraph, export: &Export) -> String {
+    let (ancestors, descendants) = match &export.focus {
+        Some(focus) => (graph.ancestors(focus), graph.descendants(focus)),
+        None => (HashSet::new(), HashSet::new()),
+    };
+    let role = |bag: &BagType| match &export.focus {
+        Some(focus) if bag == focus => Role::Focus,
+        _ if ancestors.contains(bag) => Role::Ancestor,
+        _ if descendants.contains(bag) => Role::Descendant,
+        _ => Role::Other,
+    };
+    let shown = |bag: &BagType| !export.related_only || role(bag) != Role::Other;
+
+    let bags: Vec<&BagType> = graph.bags().into_iter().filter(|b| shown(b)).collect();
+    let edges: Vec<(&BagType, u64, &BagType)> = graph
+        .rules
+        .iter()
+        .flat_map(|rule| {
+            rule.members
+                .iter()
+                .map(move |m| (&rule.container, m.quantity, &m.bag))
+        })
+        .filter(|&(container, _, member)| shown(container) && shown(member))
+        .collect();
+
+    let mut out = String::new();
+    match export.format {
+        Format::Dot => {
+            writeln!(out, "digraph bags {{").unwrap();
+            for bag in bags {
+                match role(bag) {
+                    Role::Other => writeln!(out, "    \"{}\";", bag).unwrap(),
+                    role => writeln!(
+                        out,
+                        "    \"{}\" [style=filled, fillcolor={}];",
+                        bag,
+                        role.colour()
+                    )
+                    .unwrap(),
+                }
+            }
+            for (container, quantity, member) in edges {
+                writeln!(
+                    out,
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                    container, member, quantity
+                )
+                .unwrap();
+            }
+            writeln!(out, "}}").unwrap();
+        }
+        Format::Mermaid => {
+            let id = |bag: &BagType| bag.0.replace(' ', "_");
+            writeln!(out, "graph TD").unwrap();
+            for &bag in &bags {
+                writeln!(out, "    {}[\"{}\"]", id(bag), bag).unwrap();
+            }
+            for (container, quantity, member) in edges {
+                writeln!(
+                    out,
+                    "    {} -->|{}| {}",
+                    id(container),
+                    quantity,
+                    id(member)
+                )
+                .unwrap();
+            }
+            for &highlight in &[Role::Focus, Role::Ancestor, Role::Descendant] {
+                let ids: Vec<String> = bags
+                    .iter()
+                    .filter(|&&bag| role(bag) == highlight)
+                    .map(|&bag| id(bag))
+                    .collect();
+                if ids.is_empty() {
+                    continue;
+                }
+                writeln!(
+                    out,
+                    "    classDef {} fill:{}",
+                    highlight.name(),
+                    highlight.colour()
+                )
+                .unwrap();
+                writeln!(out, "    class {} {}", ids.join(","), highlight.name()).unwrap();
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days::day_7::Day7, Solution};
+
+    const EXAMPLE: &str = "\
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+";
+
+    #[test]
+    fn dot() {
+        let rules = Day7::parse(EXAMPLE).unwrap();
+        let graph = BagGraph::new(&rules).unwrap();
+        let export = Export {
+            format: Format::Dot,
+            focus: Some("dark olive".into()),
+            related_only: false,
+        };
+        assert_eq!(
+            super::export(&graph, &export),
+            r#"digraph bags {
+    "shiny gold" [style=filled, fillcolor=lightblue];
+    "dark olive" [style=filled, fillcolor=gold];
+    "vibrant plum";
+    "faded blue" [style=filled, fillcolor=palegreen];
+    "dotted black";
+    "shiny gold" -> "dark olive" [label="1"];
+    "shiny gold" -> "vibrant plum" [label="2"];
+    "dark olive" -> "faded blue" [label="3"];
+    "vibrant plum" -> "faded blue" [label="5"];
+    "vibrant plum" -> "dotted black" [label="6"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn mermaid_related_only() {
+        let rules = Day7::parse(EXAMPLE).unwrap();
+        let graph = BagGraph::new(&rules).unwrap();
+        let export = Export {
+            format: Format::Mermaid,
+            focus: Some("dark olive".into()),
+            related_only: true,
+        };
+        assert_eq!(
+            super::export(&graph, &export),
+            r#"graph TD
+    shiny_gold["shiny gold"]
+    dark_olive["dark olive"]
+    faded_blue["faded blue"]
+    shiny_gold -->|1| dark_olive
+    dark_olive -->|3| faded_blue
+    classDef focus fill:gold
+    class dark_olive focus
+    classDef ancestor fill:lightblue
+    class shiny_gold ancestor
+    classDef descendant fill:palegreen
+    class faded_blue descendant
+"#
+        );
+        assert!("svg".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,353 @@
+use super::{BagGraph, BagType};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    str::FromStr,
+};
+
+impl<'a> BagGraph<'a> {
+    // How many of each bag there are inside `bag`, however deep down.
+    pub fn inside(&self, bag: &BagType) -> Vec<(&'a BagType, u64)> {
+        let mut counts: HashMap<&BagType, u64> = HashMap::new();
+        counts.insert(bag, 1);
+        // Containers come before their members from the top down, so each
+        // count is complete before it is passed on.
+        for &container in self.bottom_up.iter().rev() {
+            let count = match counts.get(container) {
+                Some(&count) => count,
+                None => continue,
+            };
+            for member in self.members(container) {
+                *counts.entry(&member.bag).or_default() += count * member.quantity;
+            }
+        }
+        self.bottom_up
+            .iter()
+            .rev()
+            .filter(|&&b| b != bag)
+            .filter_map(|&b| Some((b, *counts.get(b)?)))
+            .collect()
+    }
+
+    // The fewest bags from `from` down to `to`, each holding the next.  No
+    // bag holds itself, so there is no chain from a bag to itself.
+    pub fn shortest_chain(&self, from: &BagType, to: &BagType) -> Option<Vec<&'a BagType>> {
+        if from == to {
+            return None;
+        }
+        let from = self.find(from)?;
+        let mut parents: HashMap<&BagType, &BagType> = HashMap::new();
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert(from);
+        queue.push_back(from);
+
+        while let Some(bag) = queue.pop_front() {
+            if bag == to {
+                let mut chain = vec![bag];
+                while let Some(&parent) = parents.get(chain.last().unwrap()) {
+                    chain.push(parent);
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+            for member in self.members(bag) {
+                if seen.insert(&member.bag) {
+                    parents.insert(&member.bag, bag);
+                    queue.push_back(&member.bag);
+                }
+            }
+        }
+
+        None
+    }
+
+    // The most bags from `from` down to `to`, each holding the next.
+    pub fn longest_chain(&self, from: &BagType, to: &BagType) -> Option<Vec<&'a BagType>> {
+        if from == to {
+            return None;
+        }
+        // The longest way down to `to` from each bag which has one, as its
+        // length and the member it goes through.
+        let mut longest: HashMap<&BagType, (usize, Option<&BagType>)> = HashMap::new();
+        for &bag in &self.bottom_up {
+            if bag == to {
+                longest.insert(bag, (0, None));
+                continue;
+            }
+            // On a tie the first member wins, as `max_by_key` picks the
+            // last.
+            let best = self
+                .members(bag)
+                .iter()
+                .rev()
+                .filter_map(|m| Some((longest.get(&m.bag)?.0 + 1, Some(&m.bag))))
+                .max_by_key(|&(length, _)| length);
+            if let Some(best) = best {
+                longest.insert(bag, best);
+            }
+        }
+
+        let (_, mut next) = *longest.get(from)?;
+        let mut chain = vec![self.find(from)?];
+        while let Some(bag) = next {
+            chain.push(bag);
+            next = longest[bag].1;
+        }
+        Some(chain)
+    }
+
+    // How many levels of bags each bag holds: none for an empty bag, and
+    // one more than its deepest member otherwise.
+    pub fn depths(&self) -> HashMap<&'a BagType, usize> {
+        let mut depths = HashMap::new();
+        for &bag in &self.bottom_up {
+            let depth = self
+                .members(bag)
+                .iter()
+                .map(|m| depths[&m.bag] + 1)
+                .max()
+                .unwrap_or(0);
+            depths.insert(bag, depth);
+        }
+        depths
+    }
+
+    // A chain from `bag` down through the deepest member at each level.
+    fn deepest_chain(
+        &self,
+        bag: &'a BagType,
+        depths: &HashMap<&BagType, usize>,
+    ) -> Vec<&'a BagType> {
+        let mut chain = vec![bag];
+        while let Some(member) = self
+            .members(chain.last().unwrap())
+            .iter()
+            .find(|m| depths[&m.bag] + 1 == depths[chain.last().unwrap()])
+        {
+            chain.push(&member.bag);
+        }
+        chain
+    }
+}
+
+// A question about the rules, as typed on the command line.  Bag names are
+// always two words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    Holders(BagType),
+    Contents(BagType),
+    Total(BagType),
+    Chain(BagType, BagType),
+    Depth(Option<BagType>),
+}
+
+pub const HELP: &str = "\
+holders BAG      the bags which can hold BAG, however deep down
+contents BAG     the bags directly inside BAG
+total BAG        how many of each bag BAG holds, however deep down
+chain BAG BAG    the shortest and longest ways the first bag holds the second
+depth [BAG]      how many levels of bags BAG, or the deepest bag, holds";
+
+impl FromStr for Query {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let bag = |words: &[&str]| BagType(words.join(" "));
+        match words.as_slice() {
+            ["holders", rest @ ..] if rest.len() == 2 => Ok(Query::Holders(bag(rest))),
+            ["contents", rest @ ..] if rest.len() == 2 => Ok(Query::Contents(bag(rest))),
+            ["total", rest @ ..] if rest.len() == 2 => Ok(Query::Total(bag(rest))),
+            ["chain", rest @ ..] if rest.len() == 4 => {
+                Ok(Query::Chain(bag(&rest[..2]), bag(&rest[2..])))
+            }
+            ["depth"] => Ok(Query::Depth(None)),
+            ["depth", rest @ ..] if rest.len() == 2 => Ok(Query::Depth(Some(bag(rest)))),
+            [name, ..]
+                if HELP
+                    .lines()
+                    .any(|line| line.split_whitespace().next() == Some(name)) =>
+            {
+                Err(format!(
+                    "`{}` expects bag names of two words, such as `shiny gold`",
+                    name
+                ))
+            }
+            _ => Err(format!("unknown query {:?}", s.trim())),
+        }
+    }
+}
+
+impl Query {
+    // Answer the query against `graph`.
+    pub fn answer(&self, graph: &BagGraph) -> String {
+        let unknown = match self {
+            Query::Holders(bag) | Query::Contents(bag) | Query::Total(bag) => vec![bag],
+            Query::Chain(from, to) => vec![from, to],
+            Query::Depth(bag) => bag.iter().collect(),
+        }
+        .into_iter()
+        .find(|bag| graph.find(bag).is_none());
+        if let Some(bag) = unknown {
+            return format!("no rule mentions {}", bag);
+        }
+
+        match self {
+            Query::Holders(bag) => {
+                let mut holders: Vec<String> =
+                    graph.ancestors(bag).iter().map(|b| b.to_string()).collect();
+                holders.sort();
+                match holders.len() {
+                    0 => format!("no bag can hold {}", bag),
+                    n => format!("{} bag(s) can hold {}: {}", n, bag, holders.join(", ")),
+                }
+            }
+            Query::Contents(bag) => match graph.members(bag) {
+                [] => format!("{} holds no other bags", bag),
+                members => {
+                    let members: Vec<String> = members
+                        .iter()
+                        .map(|m| format!("{} {}", m.quantity, m.bag))
+                        .collect();
+                    format!("{} holds {}", bag, members.join(", "))
+                }
+            },
+            Query::Total(bag) => {
+                let mut lines = vec![format!(
+                    "{} holds {} bag(s) in all",
+                    bag,
+                    graph.total_contents(bag)
+                )];
+                lines.extend(
+                    graph
+                        .inside(bag)
+                        .iter()
+                        .map(|(inside, count)| format!("{:>8}  {}", count, inside)),
+                );
+                lines.join("\n")
+            }
+            Query::Chain(from, to) => {
+                match (
+                    graph.shortest_chain(from, to),
+                    graph.longest_chain(from, to),
+                ) {
+                    (Some(shortest), Some(longest)) => {
+                        format!(
+                            "shortest: {}\nlongest: {}",
+                            chain(&shortest),
+                            chain(&longest)
+                        )
+                    }
+                    _ => format!("{} can't hold {}", from, to),
+                }
+            }
+            Query::Depth(Some(bag)) => {
+                format!("{} holds bags {} deep", bag, graph.depths()[bag])
+            }
+            Query::Depth(None) => {
+                let depths = graph.depths();
+                // The first of the deepest bags, in the order of the rules.
+                match graph
+                    .bags()
+                    .into_iter()
+                    .rev()
+                    .max_by_key(|&bag| depths[bag])
+                {
+                    Some(deepest) => format!(
+                        "bags nest {} deep: {}",
+                        depths[deepest],
+                        chain(&graph.deepest_chain(deepest, &depths))
+                    ),
+                    None => String::from("there are no bags"),
+                }
+            }
+        }
+    }
+}
+
+fn chain(bags: &[&BagType]) -> String {
+    let names: Vec<String> = bags.iter().map(|bag| bag.to_string()).collect();
+    format!("{} ({} step(s))", names.join(" -> "), bags.len() - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days::day_7::Day7, Solution};
+
+    const EXAMPLE: &str = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+";
+
+    fn answer(query: &str) -> String {
+        let rules = Day7::parse(EXAMPLE).unwrap();
+        let graph = BagGraph::new(&rules).unwrap();
+        query.parse::<Query>().unwrap().answer(&graph)
+    }
+
+    #[test]
+    fn queries() {
+        assert_eq!(
+            answer("holders shiny gold"),
+            "4 bag(s) can hold shiny gold: bright white, dark orange, light red, muted yellow"
+        );
+        assert_eq!(answer("holders light red"), "no bag can hold light red");
+        assert_eq!(
+            answer("contents shiny gold"),
+            "shiny gold holds 1 dark olive, 2 vibrant plum"
+        );
+        assert_eq!(
+            answer("total shiny gold"),
+            "shiny gold holds 32 bag(s) in all
+       2  vibrant plum
+       1  dark olive
+      16  dotted black
+      13  faded blue"
+        );
+        assert_eq!(
+            answer("chain light red faded blue"),
+            "shortest: light red -> muted yellow -> faded blue (2 step(s))
+longest: light red -> bright white -> shiny gold -> dark olive -> faded blue (4 step(s))"
+        );
+        assert_eq!(
+            answer("chain faded blue light red"),
+            "faded blue can't hold light red"
+        );
+        assert_eq!(
+            answer("chain shiny gold shiny gold"),
+            "shiny gold can't hold shiny gold"
+        );
+        assert_eq!(answer("depth shiny gold"), "shiny gold holds bags 2 deep");
+        assert_eq!(
+            answer("depth"),
+            "bags nest 4 deep: light red -> bright white -> shiny gold -> dark olive -> faded blue (4 step(s))"
+        );
+        assert_eq!(answer("total pale mauve"), "no rule mentions pale mauve");
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(
+            "chain  light red   shiny gold".parse(),
+            Ok(Query::Chain("light red".into(), "shiny gold".into()))
+        );
+        assert!("holders shiny".parse::<Query>().is_err());
+        assert!("frobnicate".parse::<Query>().is_err());
+        assert_eq!(
+            "c".parse::<Query>(),
+            Err(String::from("unknown query \"c\""))
+        );
+        assert_eq!(
+            "hold shiny gold".parse::<Query>(),
+            Err(String::from("unknown query \"hold shiny gold\""))
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use super::{bag_rule_parser, BagGraph, BagRule, BagType, Cycle};
+use crate::{error::ParseError, parse};
+use std::collections::HashMap;
+
+// A rule and the line it is on.
+struct Line<'a> {
+    number: usize,
+    text: &'a str,
+    rule: BagRule,
+}
+
+impl Line<'_> {
+    // The text of the member at `idx`, such as `2 shiny gold bags`.
+    fn member(&self, idx: usize) -> Option<&str> {
+        let contain = " contain ";
+        let members = &self.text[self.text.find(contain)? + contain.len()..];
+        members.trim_end_matches('.').split(", ").nth(idx)
+    }
+
+    // An error pointing at the quantity of the member at `idx`.
+    fn quantity_error(&self, idx: usize, message: String) -> ParseError {
+        let column = self
+            .member(idx)
+            .map_or(1, |member| parse::column(self.text, member));
+        ParseError::new(self.text, column, message).on_line(self.number)
+    }
+
+    // An error pointing at the bag named by the member at `idx`.
+    fn bag_error(&self, idx: usize, message: String) -> ParseError {
+        let column = self
+            .member(idx)
+            .and_then(|member| Some(&member[member.find(' ')? + 1..]))
+            .map_or(1, |bag| parse::column(self.text, bag));
+        ParseError::new(self.text, column, message).on_line(self.number)
+    }
+}
+
+// Parse `input` and check that its rules fit together, returning the rules
+// which parsed and every problem found, in the order of the input.  A bag
+// must have exactly one rule, every member must be at least one bag, and no
+// bag can end up inside itself.
+pub(super) fn check(input: &str) -> (Vec<BagRule>, Vec<ParseError>) {
+    let mut lines = vec![];
+    let mut errors = vec![];
+    for (idx, text) in input.lines().enumerate() {
+        if text.trim().is_empty() {
+            continue;
+        }
+        match bag_rule_parser::bag_rule(text) {
+            Ok(rule) => lines.push(Line {
+                number: idx + 1,
+                text,
+                rule,
+            }),
+            Err(err) => errors.push(ParseError::from_peg(text, err).on_line(idx + 1)),
+        }
+    }
+
+    let mut first_rule: HashMap<&BagType, &Line> = HashMap::new();
+    for line in &lines {
+        match first_rule.get(&line.rule.container) {
+            Some(first) => errors.push(
+                ParseError::new(
+                    line.text,
+                    1,
+                    format!(
+                        "another rule for {} bags, after the one on line {}",
+                        line.rule.container, first.number
+                    ),
+                )
+                .on_line(line.number),
+            ),
+            None => {
+                first_rule.insert(&line.rule.container, line);
+            }
+        }
+    }
+
+    for line in &lines {
+        for (idx, member) in line.rule.members.iter().enumerate() {
+            if member.quantity == 0 {
+                errors
+                    .push(line.quantity_error(idx, format!("a quantity of 0 {} bags", member.bag)));
+            }
+            if !first_rule.contains_key(&member.bag) {
+                errors.push(line.bag_error(
+                    idx,
+                    format!("{} bags have no rule of their own", member.bag),
+                ));
+            }
+        }
+    }
+
+    let rules: Vec<BagRule> = lines.into_iter().map(|line| line.rule).collect();
+    if let Err(cycle) = BagGraph::new(&rules) {
+        errors.push(cycle_error(input, &cycle));
+    }
+
+    errors.sort_by_key(|err| (err.line, err.column));
+    (rules, errors)
+}
+
+// The rules in `input` if they can be queried, or every problem with them.
+pub fn validate(input: &str) -> Result<Vec<BagRule>, Vec<ParseError>> {
+    match check(input) {
+        (rules, errors) if errors.is_empty() => Ok(rules),
+        (_, errors) => Err(errors),
+    }
+}
+
+// Report `cycle` against the rule for its first bag, pointing at the
+// member which continues it.
+fn cycle_error(input: &str, cycle: &Cycle) -> ParseError {
+    let container = format!("{} bags contain ", cycle.0[0]);
+    let next = cycle.0.get(1).unwrap_or(&cycle.0[0]).to_string();
+    input
+        .lines()
+        .enumerate()
+        .find(|(_, line)| line.starts_with(&container))
+        .map(|(idx, line)| {
+            let column = line[container.len()..]
+                .find(&next)
+                .map_or(1, |offset| container.len() + offset + 1);
+            ParseError::new(line, column, cycle.to_string()).on_line(idx + 1)
+        })
+        .unwrap_or_else(|| ParseError::new("", 1, cycle.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problems() {
+        let input = "\
+light red bags contain 1 bright white bag, 0 muted yellow bags.
+bright white bags contain 2 dark red bags.
+
+muted yellow bags contain no other bags.
+light red bags contain no other bags.
+dark blue bags contain some bags.
+";
+        let problems: Vec<(usize, usize, String)> = validate(input)
+            .unwrap_err()
+            .into_iter()
+            .map(|err| (err.line, err.column, err.message))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (1, 44, String::from("a quantity of 0 muted yellow bags")),
+                (
+                    2,
+                    29,
+                    String::from("dark red bags have no rule of their own")
+                ),
+                (
+                    5,
+                    1,
+                    String::from("another rule for light red bags, after the one on line 1")
+                ),
+                (
+                    6,
+                    24,
+                    String::from("expected one of \".\", \"no other bags.\", '0'..='9'")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_member() {
+        let input = "\
+light red bags contain 1 dark blue bag, 0 dark blue bags.
+dark blue bags contain no other bags.
+";
+        let problems = validate(input).unwrap_err();
+        assert_eq!(problems.len(), 1);
+        assert_eq!((problems[0].line, problems[0].column), (1, 41));
+    }
+
+    #[test]
+    fn valid_input() {
+        assert!(validate(include_str!("../../../data/day_7.txt")).is_ok());
+    }
+}
@@ -0,0 +1,411 @@
+use crate::{
+    error::{ParseError, Result},
+    vm, Solution,
+};
+use std::{collections::HashSet, convert::TryFrom, fmt, str::FromStr};
+
+mod analysis;
+mod assembler;
+mod debugger;
+mod history;
+mod profile;
+mod repair;
+mod search;
+mod trace;
+
+pub use analysis::{analyze, Report};
+pub use assembler::{assemble, disassemble};
+pub use debugger::{Command, Debugger, Stop, HELP};
+pub use history::{History, Snapshot};
+pub use profile::{HotLoop, Profile};
+pub use repair::{repair, Repair};
+pub use search::{search, Patch, PatchSet, Search};
+pub use trace::{Diff, Trace};
+
+// The console's fixed instruction set.  `crate::vm` runs the same programs
+// with an instruction set which can be extended; `State` runs them directly,
+// as it runs every instruction of every search and repair, and a test keeps
+// the two in step.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Operation {
+    Acc,
+    Jmp,
+    Nop,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Operation::Acc => "acc",
+            Operation::Jmp => "jmp",
+            Operation::Nop => "nop",
+        })
+    }
+}
+
+impl FromStr for Operation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "acc" => Ok(Operation::Acc),
+            "jmp" => Ok(Operation::Jmp),
+            "nop" => Ok(Operation::Nop),
+            _ => Err(format!("expected `acc`, `jmp` or `nop`, found `{}`", s)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Instruction(Operation, i64);
+
+impl Instruction {
+    // The instruction with `jmp` and `nop` swapped, if it is either.
+    pub fn flipped(self) -> Option<Instruction> {
+        match self {
+            Instruction(Operation::Jmp, arg) => Some(Instruction(Operation::Nop, arg)),
+            Instruction(Operation::Nop, arg) => Some(Instruction(Operation::Jmp, arg)),
+            Instruction(Operation::Acc, _) => None,
+        }
+    }
+
+    // Where control goes after this instruction, if it is at `pc`.
+    pub fn successor(self, pc: usize) -> i64 {
+        match self {
+            Instruction(Operation::Jmp, arg) => pc as i64 + arg,
+            _ => pc as i64 + 1,
+        }
+    }
+
+    // The same instruction for `crate::vm`.
+    #[cfg(test)]
+    fn op(self) -> vm::Op<'static> {
+        let opcode: &'static dyn vm::Opcode = match self.0 {
+            Operation::Acc => &vm::ACC,
+            Operation::Jmp => &vm::JMP,
+            Operation::Nop => &vm::NOP,
+        };
+        vm::Op {
+            opcode,
+            operands: vec![vm::Operand::Number(self.1)],
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:+}", self.0, self.1)
+    }
+}
+
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    peg::parser! {
+        pub(crate) grammar parser() for str {
+            pub(crate) rule root() -> Vec<Instruction>
+                = i:instruction()+ ![_] { i }
+
+            rule instruction() -> Instruction
+                = o:operation() " " a:argument() newline()? { Instruction(o, a) }
+
+            rule operation() -> Operation
+                = "acc" { Operation::Acc }
+                / "jmp" { Operation::Jmp }
+                / "nop" { Operation::Nop }
+
+            rule argument() -> i64
+                = s:sign() n:number() { s * n }
+
+            rule sign() -> i64
+                = "+" { 1 }
+                / "-" { -1 }
+
+            rule number() -> i64
+                = n:$(['0'..='9']+) {? n.parse().or(Err("a number")) }
+
+            rule newline()
+                = ['\n' | '\r']+
+        }
+    }
+    parser::root(input).map_err(|err| ParseError::from_peg(input, err))
+}
+
+// The console's registers.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    pub accumulator: i64,
+    pub program_counter: i64,
+}
+
+impl State {
+    // Run `instruction`, leaving the state alone if the result overflows.
+    fn execute_instruction(&mut self, instruction: &Instruction) -> Result<(), vm::Fault> {
+        let (accumulator, offset) = match instruction {
+            Instruction(Operation::Acc, arg) => (self.accumulator.checked_add(*arg), 1),
+            Instruction(Operation::Jmp, arg) => (Some(self.accumulator), *arg),
+            Instruction(Operation::Nop, _) => (Some(self.accumulator), 1),
+        };
+        let accumulator = accumulator.ok_or(vm::Fault::Overflow)?;
+        self.program_counter = self
+            .program_counter
+            .checked_add(offset)
+            .ok_or(vm::Fault::Overflow)?;
+        self.accumulator = accumulator;
+        Ok(())
+    }
+}
+
+// How a run of a program ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    // The program counter reached the instruction just past the end.
+    Halted { acc: i64 },
+    // The instruction at `pc` was about to run for a second time.
+    InfiniteLoop { pc: i64, acc: i64 },
+    // A jump went somewhere other than an instruction or the end.
+    OutOfBounds { pc: i64 },
+    // The instruction at `pc` would take the accumulator or the program
+    // counter past what 64 bits hold.
+    Overflow { pc: i64 },
+    // The step budget ran out first.
+    FuelExhausted,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Halted { acc } => write!(f, "halted with acc {}", acc),
+            Outcome::InfiniteLoop { pc, acc } => {
+                write!(f, "looped at pc {} with acc {}", pc, acc)
+            }
+            Outcome::OutOfBounds { pc } => write!(f, "jumped out of bounds to pc {}", pc),
+            Outcome::Overflow { pc } => write!(f, "overflowed at pc {}", pc),
+            Outcome::FuelExhausted => f.write_str("ran out of fuel"),
+        }
+    }
+}
+
+// Run `instructions` for at most `fuel` steps.  Loop detection alone
+// bounds a run to one step per instruction, so the budget only matters
+// for callers which want to stop sooner than that.
+pub fn execute(instructions: &[Instruction], fuel: usize) -> Outcome {
+    execute_with(instructions, fuel, |_| {})
+}
+
+// One instruction as it was executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub pc: i64,
+    pub instruction: Instruction,
+    pub acc_before: i64,
+    pub acc_after: i64,
+}
+
+// Like `execute`, calling `on_step` after each instruction runs.
+pub fn execute_with(
+    instructions: &[Instruction],
+    mut fuel: usize,
+    mut on_step: impl FnMut(&Step),
+) -> Outcome {
+    let mut executed_instructions: HashSet<i64> = Default::default();
+    let mut state: State = Default::default();
+
+    loop {
+        let pc = state.program_counter;
+        if pc == instructions.len() as i64 {
+            return Outcome::Halted {
+                acc: state.accumulator,
+            };
+        }
+        let instruction = match usize::try_from(pc).ok().and_then(|pc| instructions.get(pc)) {
+            Some(instruction) => instruction,
+            None => return Outcome::OutOfBounds { pc },
+        };
+        if !executed_instructions.insert(pc) {
+            return Outcome::InfiniteLoop {
+                pc,
+                acc: state.accumulator,
+            };
+        }
+        if fuel == 0 {
+            return Outcome::FuelExhausted;
+        }
+        fuel -= 1;
+        let acc_before = state.accumulator;
+        if state.execute_instruction(instruction).is_err() {
+            return Outcome::Overflow { pc };
+        }
+        on_step(&Step {
+            pc,
+            instruction: *instruction,
+            acc_before,
+            acc_after: state.accumulator,
+        });
+    }
+}
+
+// Try flipping each `jmp` and `nop` in turn until the program terminates.
+// `repair` is much faster; this is kept to check it against.
+pub fn repair_by_brute_force(instructions: &[Instruction]) -> Option<i64> {
+    let fixable_instructions: Vec<(usize, &Instruction)> = instructions
+        .iter()
+        .enumerate()
+        .filter(|(_idx, Instruction(op, _arg))| match op {
+            Operation::Jmp | Operation::Nop => true,
+            Operation::Acc => false,
+        })
+        .collect();
+
+    for (fixable_instruction_index, fixable_instruction) in fixable_instructions {
+        let mut executed_instructions: HashSet<i64> = Default::default();
+        let mut state: State = Default::default();
+
+        loop {
+            if executed_instructions.contains(&state.program_counter) {
+                break;
+            }
+
+            if state.program_counter as usize == instructions.len() {
+                return Some(state.accumulator);
+            }
+
+            let instruction = if state.program_counter as usize == fixable_instruction_index {
+                match fixable_instruction {
+                    Instruction(Operation::Jmp, arg) => Instruction(Operation::Nop, *arg),
+                    Instruction(Operation::Nop, arg) => Instruction(Operation::Jmp, *arg),
+                    Instruction(Operation::Acc, _) => panic!("Acc instructions can't be fixed"),
+                }
+            } else {
+                match instructions.get(state.program_counter as usize) {
+                    Some(&instruction) => instruction,
+                    None => break,
+                }
+            };
+
+            executed_instructions.insert(state.program_counter);
+            if state.execute_instruction(&instruction).is_err() {
+                break;
+            }
+        }
+    }
+
+    None
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Instruction>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_instructions(input)?)
+    }
+
+    // The accumulator just before the program loops, if it does.
+    fn part1(instructions: &Self::Input) -> Option<i64> {
+        match execute(instructions, usize::MAX) {
+            Outcome::InfiniteLoop { acc, .. } => Some(acc),
+            _ => None,
+        }
+    }
+
+    fn part2(instructions: &Self::Input) -> Option<i64> {
+        repair(instructions).map(|repair| repair.accumulator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+
+    #[test]
+    fn example() {
+        let instructions = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part1(&instructions), Some(5));
+        assert_eq!(Day8::part2(&instructions), Some(8));
+    }
+
+    #[test]
+    fn matches_vm() {
+        let states = [
+            State::default(),
+            State {
+                accumulator: -7,
+                program_counter: 3,
+            },
+            State {
+                accumulator: i64::MAX,
+                program_counter: i64::MAX,
+            },
+            State {
+                accumulator: i64::MIN,
+                program_counter: i64::MIN,
+            },
+        ];
+        for &operation in &[Operation::Acc, Operation::Jmp, Operation::Nop] {
+            for &arg in &[0, 1, -1, i64::MAX, i64::MIN] {
+                let instruction = Instruction(operation, arg);
+                for &state in &states {
+                    let mut machine = vm::Machine {
+                        pc: state.program_counter,
+                        ..vm::Machine::default()
+                    };
+                    machine
+                        .registers
+                        .insert(String::from("acc"), state.accumulator);
+                    let expected = machine.execute(&instruction.op()).map(|_| State {
+                        accumulator: machine.register("acc"),
+                        program_counter: machine.pc,
+                    });
+                    let mut actual = state;
+                    let actual = actual.execute_instruction(&instruction).map(|_| actual);
+                    assert_eq!(actual, expected, "{} from {:?}", instruction, state);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn no_answer() {
+        let halts = Day8::parse("acc +1\n").unwrap();
+        assert_eq!(Day8::part1(&halts), None);
+        assert_eq!(Day8::part2(&halts), None);
+    }
+
+    #[test]
+    fn outcomes() {
+        let program = parse_instructions("nop +0\njmp +2\nacc +1\nacc +2\n").unwrap();
+        assert_eq!(execute(&program, 10), Outcome::Halted { acc: 2 });
+        assert_eq!(execute(&program, 3), Outcome::Halted { acc: 2 });
+        assert_eq!(execute(&program, 2), Outcome::FuelExhausted);
+        let program = parse_instructions("acc +3\njmp -1\n").unwrap();
+        assert_eq!(
+            execute(&program, 10),
+            Outcome::InfiniteLoop { pc: 0, acc: 3 }
+        );
+        let program = parse_instructions("acc +3\njmp -2\n").unwrap();
+        assert_eq!(execute(&program, 10), Outcome::OutOfBounds { pc: -1 });
+        let program = parse_instructions("acc +9223372036854775807\nacc +1\n").unwrap();
+        assert_eq!(execute(&program, 10), Outcome::Overflow { pc: 1 });
+    }
+
+    #[test]
+    fn input() {
+        let instructions = Day8::parse(include_str!("../../data/day_8.txt")).unwrap();
+        assert_eq!(Day8::part1(&instructions), Some(1394));
+        assert_eq!(Day8::part2(&instructions), Some(1626));
+    }
+}
@@ -0,0 +1,233 @@
+use super::{execute, Instruction, Outcome};
+use std::{collections::HashSet, fmt};
+
+// The index of the instruction at `pc`, or of the end of the program,
+// if `pc` is either.
+pub(super) fn target(instructions: &[Instruction], pc: i64) -> Option<usize> {
+    match pc {
+        pc if pc >= 0 && pc as usize <= instructions.len() => Some(pc as usize),
+        _ => None,
+    }
+}
+
+// Whether the unpatched program terminates when started from each
+// instruction, and from the end.  Every instruction has exactly one
+// successor, so these are found by walking the control-flow graph
+// backwards from the end.
+pub(super) fn terminates(instructions: &[Instruction]) -> Vec<bool> {
+    let end = instructions.len();
+    let mut predecessors = vec![vec![]; end + 1];
+    for (idx, &instruction) in instructions.iter().enumerate() {
+        if let Some(target) = target(instructions, instruction.successor(idx)) {
+            predecessors[target].push(idx);
+        }
+    }
+
+    let mut terminates = vec![false; end + 1];
+    let mut stack = vec![end];
+    while let Some(idx) = stack.pop() {
+        terminates[idx] = true;
+        stack.extend(&predecessors[idx]);
+    }
+    terminates
+}
+
+// Each cycle in the control-flow graph in the order it runs, starting from
+// its lowest index, with the cycles in order of those indices.
+pub(super) fn cycles(instructions: &[Instruction]) -> Vec<Vec<usize>> {
+    let end = instructions.len();
+    let successor =
+        |idx: usize| target(instructions, instructions[idx].successor(idx)).filter(|&t| t < end);
+
+    // Follow the successors from each instruction in turn, marking where
+    // each walk has been so that every instruction is only walked once.
+    // A walk which comes back to itself has found a cycle.
+    let mut cycles = vec![];
+    let mut walked = vec![None; end];
+    for start in 0..end {
+        let mut idx = Some(start);
+        while let Some(current) = idx {
+            if walked[current].is_some() {
+                break;
+            }
+            walked[current] = Some(start);
+            idx = successor(current);
+        }
+        if let Some(current) = idx {
+            if walked[current] == Some(start) {
+                let mut cycle = vec![current];
+                let mut next = successor(current).unwrap();
+                while next != current {
+                    cycle.push(next);
+                    next = successor(next).unwrap();
+                }
+                let lowest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+                cycle.rotate_left(lowest);
+                cycles.push(cycle);
+            }
+        }
+    }
+    cycles.sort();
+    cycles
+}
+
+// What can be said about a program without running it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    // Instructions which are never reached from the first.
+    pub unreachable: Vec<usize>,
+    // Each cycle in the control-flow graph in the order it runs, starting
+    // from its lowest index.  A program only runs into one of them.
+    pub cycles: Vec<Vec<usize>>,
+    // Instructions which jump somewhere other than an instruction or the
+    // end, and where to.
+    pub out_of_bounds: Vec<(usize, i64)>,
+    // The `jmp`s and `nop`s which would make the program terminate if
+    // flipped on their own.
+    pub terminating_flips: Vec<usize>,
+}
+
+pub fn analyze(instructions: &[Instruction]) -> Report {
+    let end = instructions.len();
+    let successor =
+        |idx: usize| target(instructions, instructions[idx].successor(idx)).filter(|&t| t < end);
+
+    // The instructions the program runs, starting from the first.
+    let mut on_path = HashSet::new();
+    let mut idx = Some(0).filter(|_| end > 0);
+    while let Some(current) = idx {
+        if !on_path.insert(current) {
+            break;
+        }
+        idx = successor(current);
+    }
+
+    let cycles = cycles(instructions);
+
+    let out_of_bounds = instructions
+        .iter()
+        .enumerate()
+        .map(|(idx, instruction)| (idx, instruction.successor(idx)))
+        .filter(|&(_, pc)| target(instructions, pc).is_none())
+        .collect();
+
+    // If the program doesn't terminate, only a flip on its path can change
+    // that, and does if it leads somewhere which terminates: it can't lead
+    // back to the flip, or the flip would terminate unflipped and so would
+    // the program.  If the program does terminate, that argument fails, but
+    // flipping anything off its path keeps it terminating.
+    let terminates = terminates(instructions);
+    let terminating_flips = instructions
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, &instruction)| Some((idx, instruction.flipped()?)))
+        .filter(
+            |&(idx, flipped)| match (terminates[0], on_path.contains(&idx)) {
+                (false, true) => target(instructions, flipped.successor(idx))
+                    .is_some_and(|target| terminates[target]),
+                (false, false) => false,
+                (true, true) => {
+                    let mut patched = instructions.to_vec();
+                    patched[idx] = flipped;
+                    matches!(execute(&patched, usize::MAX), Outcome::Halted { .. })
+                }
+                (true, false) => true,
+            },
+        )
+        .map(|(idx, _)| idx)
+        .collect();
+
+    Report {
+        unreachable: (0..end).filter(|idx| !on_path.contains(idx)).collect(),
+        cycles,
+        out_of_bounds,
+        terminating_flips,
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |indices: &[usize]| match indices {
+            [] => String::from("none"),
+            _ => indices
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        writeln!(
+            f,
+            "unreachable: {} instruction(s): {}",
+            self.unreachable.len(),
+            list(&self.unreachable)
+        )?;
+        writeln!(f, "cycles: {}", self.cycles.len())?;
+        for cycle in &self.cycles {
+            writeln!(f, "  {} instruction(s): {}", cycle.len(), list(cycle))?;
+        }
+        let out_of_bounds: Vec<String> = self
+            .out_of_bounds
+            .iter()
+            .map(|(idx, pc)| format!("{} -> {}", idx, pc))
+            .collect();
+        writeln!(
+            f,
+            "out of bounds: {}",
+            match out_of_bounds.len() {
+                0 => String::from("none"),
+                _ => out_of_bounds.join(", "),
+            }
+        )?;
+        writeln!(f, "terminating flips: {}", list(&self.terminating_flips))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_8::{parse_instructions, repair};
+
+    #[test]
+    fn example() {
+        let program = parse_instructions(
+            "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n",
+        )
+        .unwrap();
+        let report = analyze(&program);
+        assert_eq!(report.unreachable, vec![5, 8]);
+        assert_eq!(report.cycles, vec![vec![1, 2, 6, 7, 3, 4]]);
+        assert!(report.out_of_bounds.is_empty());
+        assert_eq!(report.terminating_flips, vec![7]);
+    }
+
+    #[test]
+    fn input() {
+        let program = parse_instructions(include_str!("../../../data/day_8.txt")).unwrap();
+        let report = analyze(&program);
+        assert_eq!(
+            report.terminating_flips,
+            vec![repair(&program).unwrap().index]
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_and_terminating_programs() {
+        let program = parse_instructions("jmp +2\njmp -5\nnop +0\njmp -1\n").unwrap();
+        let report = analyze(&program);
+        assert_eq!(report.out_of_bounds, vec![(1, -4)]);
+        assert_eq!(report.cycles, vec![vec![2, 3]]);
+        assert_eq!(report.unreachable, vec![1]);
+        assert_eq!(report.terminating_flips, vec![3]);
+
+        let program = parse_instructions("nop +0\njmp -1\nacc +1\n").unwrap();
+        let report = analyze(&program);
+        assert_eq!(report.cycles, vec![vec![0, 1]]);
+        assert_eq!(report.terminating_flips, vec![1]);
+
+        let program = parse_instructions("jmp +2\nnop -1\nnop -2\n").unwrap();
+        let report = analyze(&program);
+        assert!(report.cycles.is_empty());
+        assert_eq!(report.terminating_flips, vec![0, 1]);
+    }
+}
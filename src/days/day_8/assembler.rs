@@ -0,0 +1,182 @@
+use super::{Instruction, Operation};
+use crate::{error::ParseError, parse};
+use std::collections::HashMap;
+
+// Write `program` out in the puzzle's input format, which
+// `parse_instructions` reads back as the same program.
+pub fn disassemble(program: &[Instruction]) -> String {
+    program
+        .iter()
+        .map(|instruction| format!("{}\n", instruction))
+        .collect()
+}
+
+// An instruction whose argument may still be a label.
+struct Pending<'a> {
+    line: usize,
+    text: &'a str,
+    operation: Operation,
+    argument: &'a str,
+}
+
+// Assemble a program written in the input format extended for editing by
+// hand:
+//
+//     # Comments run from `#` or `;` to the end of the line.
+//     start:  nop +0
+//             acc +1
+//             jmp done    ; A label is resolved to the relative offset.
+//             jmp start
+//     done:
+//
+// Blank lines are ignored.  A label names the instruction after it, or
+// the end of the program if there is none, and can be the argument of a
+// `jmp` or `nop` but not of an `acc`.
+pub fn assemble(source: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut pending = vec![];
+
+    for (idx, text) in source.lines().enumerate() {
+        let line = idx + 1;
+        let mut code = match text.find(['#', ';']) {
+            Some(comment) => &text[..comment],
+            None => text,
+        }
+        .trim();
+
+        while let Some(colon) = code.find(':') {
+            let label = code[..colon].trim();
+            if !is_label(label) {
+                return Err(ParseError::new(
+                    text,
+                    parse::column(text, label),
+                    format!("invalid label `{}`", label),
+                )
+                .on_line(line));
+            }
+            if labels.insert(label, pending.len()).is_some() {
+                return Err(ParseError::new(
+                    text,
+                    parse::column(text, label),
+                    format!("label `{}` is already defined", label),
+                )
+                .on_line(line));
+            }
+            code = code[colon + 1..].trim();
+        }
+        if code.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = code.split_whitespace().collect();
+        if fields.len() != 2 {
+            return Err(ParseError::new(
+                text,
+                parse::column(text, code),
+                "expected an operation and an argument",
+            )
+            .on_line(line));
+        }
+        let operation =
+            parse::field(text, fields[0], "invalid operation").map_err(|err| err.on_line(line))?;
+        pending.push(Pending {
+            line,
+            text,
+            operation,
+            argument: fields[1],
+        });
+    }
+
+    pending
+        .iter()
+        .enumerate()
+        .map(|(address, instruction)| {
+            let Pending {
+                line,
+                text,
+                operation,
+                argument,
+            } = *instruction;
+            let offset =
+                if argument.starts_with(|c: char| c == '+' || c == '-' || c.is_ascii_digit()) {
+                    parse::field(text, argument, "invalid offset")
+                } else if let Operation::Acc = operation {
+                    Err(ParseError::new(
+                        text,
+                        parse::column(text, argument),
+                        "`acc` takes a number, not a label",
+                    ))
+                } else {
+                    match labels.get(argument) {
+                        Some(&target) => Ok(target as i64 - address as i64),
+                        None => Err(ParseError::new(
+                            text,
+                            parse::column(text, argument),
+                            format!("undefined label `{}`", argument),
+                        )),
+                    }
+                };
+            offset
+                .map(|offset| Instruction(operation, offset))
+                .map_err(|err| err.on_line(line))
+        })
+        .collect()
+}
+
+fn is_label(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_8::parse_instructions;
+
+    #[test]
+    fn round_trip() {
+        let input = include_str!("../../../data/day_8.txt");
+        let program = parse_instructions(input).unwrap();
+        assert_eq!(disassemble(&program).trim_end(), input.trim_end());
+        assert_eq!(assemble(input).unwrap(), program);
+    }
+
+    #[test]
+    fn labels() {
+        let source = "\
+# The example from the puzzle.
+start:  nop +0
+skip:   acc +1
+        jmp six     ; +4
+
+        acc +3
+        jmp skip
+        acc -99
+six:    acc +1
+        jmp -4
+        acc +6
+";
+        let program = assemble(source).unwrap();
+        assert_eq!(
+            disassemble(&program),
+            "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let err = assemble("nop +0\njmp nowhere\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        let err = assemble("a: nop +0\na: nop +0\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = assemble("end:\nacc end\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        let err = assemble("  mul +2\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 3));
+        let err = assemble("\nstart: jmp start + 1\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 8));
+    }
+}
@@ -0,0 +1,601 @@
+use super::{History, Instruction, Snapshot};
+use crate::error::ParseError;
+use std::{fmt, str::FromStr};
+
+// An expression over the console's registers, used for breakpoint
+// conditions and watches.  Comparisons are 1 when true and 0 otherwise,
+// and a condition holds when it is not 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Number(i64),
+    Acc,
+    Pc,
+    Steps,
+    Neg(Box<Expr>),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+fn binary(lhs: Expr, op: BinaryOp, rhs: Expr) -> Expr {
+    Expr::Binary(Box::new(lhs), op, Box::new(rhs))
+}
+
+peg::parser! {
+    grammar expression() for str {
+        rule _() = [' ' | '\t']*
+
+        pub(crate) rule expr() -> Expr
+            = _() e:operation() _() { e }
+
+        rule operation() -> Expr = precedence! {
+            x:(@) _() "==" _() y:@ { binary(x, BinaryOp::Eq, y) }
+            x:(@) _() "!=" _() y:@ { binary(x, BinaryOp::Ne, y) }
+            x:(@) _() "<=" _() y:@ { binary(x, BinaryOp::Le, y) }
+            x:(@) _() "<" _() y:@ { binary(x, BinaryOp::Lt, y) }
+            x:(@) _() ">=" _() y:@ { binary(x, BinaryOp::Ge, y) }
+            x:(@) _() ">" _() y:@ { binary(x, BinaryOp::Gt, y) }
+            --
+            x:(@) _() "+" _() y:@ { binary(x, BinaryOp::Add, y) }
+            x:(@) _() "-" _() y:@ { binary(x, BinaryOp::Sub, y) }
+            --
+            x:(@) _() "*" _() y:@ { binary(x, BinaryOp::Mul, y) }
+            --
+            "-" _() x:@ { Expr::Neg(Box::new(x)) }
+            --
+            n:number() { Expr::Number(n) }
+            "acc" { Expr::Acc }
+            "pc" { Expr::Pc }
+            "steps" { Expr::Steps }
+            "(" _() e:operation() _() ")" { e }
+        }
+
+        rule number() -> i64
+            = n:$(['0'..='9']+) {? n.parse().or(Err("a number")) }
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        expression::expr(s).map_err(|err| ParseError::from_peg(s, err))
+    }
+}
+
+impl Expr {
+    fn evaluate(&self, debugger: &Debugger) -> i64 {
+        match self {
+            Expr::Number(n) => *n,
+            Expr::Acc => debugger.accumulator(),
+            Expr::Pc => debugger.program_counter(),
+            Expr::Steps => debugger.steps() as i64,
+            Expr::Neg(x) => x.evaluate(debugger).wrapping_neg(),
+            Expr::Binary(x, op, y) => {
+                let (x, y) = (x.evaluate(debugger), y.evaluate(debugger));
+                match op {
+                    BinaryOp::Add => x.wrapping_add(y),
+                    BinaryOp::Sub => x.wrapping_sub(y),
+                    BinaryOp::Mul => x.wrapping_mul(y),
+                    BinaryOp::Eq => (x == y) as i64,
+                    BinaryOp::Ne => (x != y) as i64,
+                    BinaryOp::Lt => (x < y) as i64,
+                    BinaryOp::Le => (x <= y) as i64,
+                    BinaryOp::Gt => (x > y) as i64,
+                    BinaryOp::Ge => (x >= y) as i64,
+                }
+            }
+        }
+    }
+}
+
+// An expression along with the text it was written as, for display.
+#[derive(Debug, Clone)]
+pub struct Named {
+    pub text: String,
+    pub expr: Expr,
+}
+
+impl FromStr for Named {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            text: s.trim().to_string(),
+            expr: s.parse()?,
+        })
+    }
+}
+
+// Why execution stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    // A single step finished.
+    Step,
+    // The breakpoint with this index holds.
+    Breakpoint(usize),
+    // The instruction at `pc` has run before and is about to run again, so
+    // the program will never terminate.  This is reported every time the
+    // program comes back round, so that continuing can't run forever.
+    Loop { pc: i64 },
+    // The program counter is just past the last instruction.
+    Halted,
+    OutOfBounds { pc: i64 },
+    // The instruction at `pc` would overflow.
+    Overflow { pc: i64 },
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stop::Step => f.write_str("stepped"),
+            Stop::Breakpoint(idx) => write!(f, "hit breakpoint {}", idx),
+            Stop::Loop { pc } => write!(f, "about to repeat pc {}: the program loops", pc),
+            Stop::Halted => f.write_str("the program terminated"),
+            Stop::OutOfBounds { pc } => write!(f, "pc {} is outside the program", pc),
+            Stop::Overflow { pc } => write!(f, "the instruction at pc {} overflows", pc),
+        }
+    }
+}
+
+// How many steps the debugger can undo before going back further has to
+// replay the program from the start.
+const UNDO_LIMIT: usize = 10_000;
+
+// Steps through a boot code program, forwards or backwards, stopping at
+// breakpoints and at the point where it starts to loop.
+pub struct Debugger<'a> {
+    program: &'a [Instruction],
+    history: History<'a>,
+    breakpoints: Vec<Option<Named>>,
+    watches: Vec<Option<Named>>,
+}
+
+impl<'a> Debugger<'a> {
+    pub fn new(program: &'a [Instruction]) -> Self {
+        Self {
+            program,
+            history: History::new(program, UNDO_LIMIT),
+            breakpoints: vec![],
+            watches: vec![],
+        }
+    }
+
+    // Go back to the start of the program, keeping breakpoints and
+    // watches.
+    pub fn reset(&mut self) {
+        self.history.reset();
+    }
+
+    pub fn accumulator(&self) -> i64 {
+        self.history.state().accumulator
+    }
+
+    pub fn program_counter(&self) -> i64 {
+        self.history.state().program_counter
+    }
+
+    pub fn steps(&self) -> usize {
+        self.history.steps()
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        self.history.snapshot()
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.history.restore(snapshot);
+    }
+
+    fn current(&self) -> Option<&'a Instruction> {
+        self.history.current()
+    }
+
+    // Why the program can't go any further, if it can't.
+    fn finished(&self) -> Option<Stop> {
+        match self.current() {
+            Some(instruction)
+                if self
+                    .history
+                    .state()
+                    .execute_instruction(instruction)
+                    .is_err() =>
+            {
+                Some(Stop::Overflow {
+                    pc: self.program_counter(),
+                })
+            }
+            Some(_) => None,
+            None if self.program_counter() == self.program.len() as i64 => Some(Stop::Halted),
+            None => Some(Stop::OutOfBounds {
+                pc: self.program_counter(),
+            }),
+        }
+    }
+
+    pub fn step(&mut self) -> Stop {
+        if !self.history.step() {
+            return self.finished().unwrap();
+        }
+        self.finished().unwrap_or(Stop::Step)
+    }
+
+    // Go back `count` steps, or to the start if there are fewer.  Returns
+    // how many steps were undone.
+    pub fn back(&mut self, count: usize) -> usize {
+        let target = self.steps().saturating_sub(count);
+        let undone = self.steps() - target;
+        self.history.goto(target);
+        undone
+    }
+
+    // Step until a breakpoint holds, an instruction is about to run again
+    // or the program can't go any further.  Breakpoints are checked
+    // between instructions, so one which holds already doesn't stop the
+    // first step.
+    pub fn cont(&mut self) -> Stop {
+        loop {
+            match self.step() {
+                Stop::Step => {}
+                stop => return stop,
+            }
+            if let Some(idx) = self.breakpoints.iter().position(|breakpoint| {
+                breakpoint
+                    .as_ref()
+                    .is_some_and(|b| b.expr.evaluate(self) != 0)
+            }) {
+                return Stop::Breakpoint(idx);
+            }
+            if self.history.has_run(self.program_counter()) {
+                return Stop::Loop {
+                    pc: self.program_counter(),
+                };
+            }
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Named) -> usize {
+        self.breakpoints.push(Some(breakpoint));
+        self.breakpoints.len() - 1
+    }
+
+    pub fn remove_breakpoint(&mut self, idx: usize) -> bool {
+        self.breakpoints
+            .get_mut(idx)
+            .and_then(Option::take)
+            .is_some()
+    }
+
+    pub fn add_watch(&mut self, watch: Named) -> usize {
+        self.watches.push(Some(watch));
+        self.watches.len() - 1
+    }
+
+    pub fn remove_watch(&mut self, idx: usize) -> bool {
+        self.watches.get_mut(idx).and_then(Option::take).is_some()
+    }
+
+    // The value of every watch expression, as `text = value` lines.
+    pub fn watches(&self) -> Vec<String> {
+        self.watches
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, watch)| {
+                let watch = watch.as_ref()?;
+                Some(format!(
+                    "watch {}: {} = {}",
+                    idx,
+                    watch.text,
+                    watch.expr.evaluate(self)
+                ))
+            })
+            .collect()
+    }
+
+    // The instructions up to `context` either side of the program counter,
+    // with the current one marked by `=>` and breakpoints on a `pc` by `*`.
+    pub fn list(&self, context: usize) -> String {
+        let pc = self.program_counter();
+        let first = (pc - context as i64).max(0);
+        let last = (pc + context as i64).min(self.program.len() as i64 - 1);
+        let mut listing = String::new();
+        for idx in first..=last {
+            let marker = if idx == pc { "=>" } else { "  " };
+            let breakpoint = if self.breaks_at(idx) { '*' } else { ' ' };
+            listing.push_str(&format!(
+                "{}{}{:>5}  {}\n",
+                marker, breakpoint, idx, self.program[idx as usize]
+            ));
+        }
+        if self.current().is_none() {
+            listing.push_str(&format!("=> {:>5}  <outside the program>\n", pc));
+        }
+        listing
+    }
+
+    fn breaks_at(&self, pc: i64) -> bool {
+        let target = binary(Expr::Pc, BinaryOp::Eq, Expr::Number(pc));
+        self.breakpoints
+            .iter()
+            .flatten()
+            .any(|breakpoint| breakpoint.expr == target)
+    }
+
+    // A one line summary of where execution is.
+    pub fn position(&self) -> String {
+        let instruction = match self.current() {
+            Some(instruction) => instruction.to_string(),
+            None => String::from("<outside the program>"),
+        };
+        format!(
+            "pc {} acc {} steps {}: {}",
+            self.program_counter(),
+            self.accumulator(),
+            self.steps(),
+            instruction
+        )
+    }
+
+    // Carry out `command` and describe what happened.
+    pub fn execute(&mut self, command: &Command) -> String {
+        let mut output = vec![];
+        match command {
+            Command::Step(count) => {
+                let mut stop = Stop::Step;
+                for _ in 0..*count {
+                    stop = self.step();
+                    if stop != Stop::Step {
+                        break;
+                    }
+                }
+                output.push(stop.to_string());
+                output.push(self.position());
+                output.extend(self.watches());
+            }
+            Command::Back(count) => {
+                let undone = self.back(*count);
+                output.push(format!("went back {} steps", undone));
+                output.push(self.position());
+                output.extend(self.watches());
+            }
+            Command::Goto(step) => {
+                if !self.history.goto(*step) {
+                    output.push(self.finished().unwrap().to_string());
+                }
+                output.push(self.position());
+                output.extend(self.watches());
+            }
+            Command::Continue => {
+                output.push(self.cont().to_string());
+                output.push(self.position());
+                output.extend(self.watches());
+            }
+            Command::Break(breakpoint) => {
+                let text = breakpoint.text.clone();
+                let idx = self.add_breakpoint(breakpoint.clone());
+                output.push(format!("breakpoint {}: {}", idx, text));
+            }
+            Command::Delete(idx) => output.push(match self.remove_breakpoint(*idx) {
+                true => format!("deleted breakpoint {}", idx),
+                false => format!("no breakpoint {}", idx),
+            }),
+            Command::Watch(watch) => {
+                let idx = self.add_watch(watch.clone());
+                output.push(format!(
+                    "watch {}: {} = {}",
+                    idx,
+                    watch.text,
+                    watch.expr.evaluate(self)
+                ));
+            }
+            Command::Unwatch(idx) => output.push(match self.remove_watch(*idx) {
+                true => format!("deleted watch {}", idx),
+                false => format!("no watch {}", idx),
+            }),
+            Command::List(context) => output.push(self.list(*context).trim_end().to_string()),
+            Command::Info => {
+                output.push(self.position());
+                for (idx, breakpoint) in self.breakpoints.iter().enumerate() {
+                    if let Some(breakpoint) = breakpoint {
+                        output.push(format!("breakpoint {}: {}", idx, breakpoint.text));
+                    }
+                }
+                output.extend(self.watches());
+            }
+            Command::Reset => {
+                self.reset();
+                output.push(self.position());
+            }
+        }
+        output.join("\n")
+    }
+}
+
+// A debugger command, as typed at its prompt.
+#[derive(Debug, Clone)]
+pub enum Command {
+    Step(usize),
+    Back(usize),
+    Goto(usize),
+    Continue,
+    // `break 7` is short for `break pc == 7`.
+    Break(Named),
+    Delete(usize),
+    Watch(Named),
+    Unwatch(usize),
+    List(usize),
+    Info,
+    Reset,
+}
+
+pub const HELP: &str = "\
+step [N]       s    run N instructions (default 1)
+back [N]            undo N instructions (default 1)
+goto STEP           go back or forward to the point after STEP steps
+continue       c    run until a breakpoint, a loop or the end
+break EXPR     b    stop when EXPR is not 0; a bare number is a pc
+delete N            remove breakpoint N
+watch EXPR     w    show EXPR whenever execution stops
+unwatch N           remove watch N
+list [N]       l    show N instructions either side of pc (default 3)
+info           i    show the registers, breakpoints and watches
+reset               start the program again
+quit           q    leave the debugger
+
+Expressions use acc, pc, steps, integers, + - * and comparisons
+such as `acc > 100` or `pc == 7`.";
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (name, rest) = match s.find(' ') {
+            Some(idx) => (&s[..idx], s[idx + 1..].trim()),
+            None => (s, ""),
+        };
+        let number = |default: Option<usize>| match (rest, default) {
+            ("", Some(default)) => Ok(default),
+            _ => rest
+                .parse()
+                .map_err(|_| format!("`{}` expects a number, got {:?}", name, rest)),
+        };
+        let expression =
+            || -> Result<Named, String> { rest.parse().map_err(|err: ParseError| err.to_string()) };
+
+        match name {
+            "s" | "step" => Ok(Command::Step(number(Some(1))?)),
+            "back" => Ok(Command::Back(number(Some(1))?)),
+            "goto" => Ok(Command::Goto(number(None)?)),
+            "c" | "continue" => Ok(Command::Continue),
+            "b" | "break" => match rest.parse::<i64>() {
+                Ok(pc) => Ok(Command::Break(Named {
+                    text: format!("pc == {}", pc),
+                    expr: binary(Expr::Pc, BinaryOp::Eq, Expr::Number(pc)),
+                })),
+                Err(_) => Ok(Command::Break(expression()?)),
+            },
+            "delete" => Ok(Command::Delete(number(None)?)),
+            "w" | "watch" => Ok(Command::Watch(expression()?)),
+            "unwatch" => Ok(Command::Unwatch(number(None)?)),
+            "l" | "list" => Ok(Command::List(number(Some(3))?)),
+            "i" | "info" => Ok(Command::Info),
+            "reset" => Ok(Command::Reset),
+            _ => Err(format!("unknown command {:?}, try `help`", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_8::parse_instructions;
+
+    const EXAMPLE: &str = "\
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+
+    #[test]
+    fn expressions() {
+        let program = parse_instructions(EXAMPLE).unwrap();
+        let mut debugger = Debugger::new(&program);
+        debugger.step();
+        debugger.step();
+        let eval = |text: &str| text.parse::<Expr>().unwrap().evaluate(&debugger);
+        assert_eq!(eval("acc"), 1);
+        assert_eq!(eval("pc * 2 + -acc"), 3);
+        assert_eq!(eval("acc == 1"), 1);
+        assert_eq!(eval("(pc - 3) * 2 >= 0"), 0);
+        assert!("acc +".parse::<Expr>().is_err());
+    }
+
+    #[test]
+    fn stops_before_overflow() {
+        let program = parse_instructions("acc +9223372036854775807\nacc +1\n").unwrap();
+        let mut debugger = Debugger::new(&program);
+        assert_eq!(debugger.cont(), Stop::Overflow { pc: 1 });
+        assert_eq!(debugger.step(), Stop::Overflow { pc: 1 });
+        assert_eq!(debugger.steps(), 1);
+    }
+
+    #[test]
+    fn stops_at_loop_and_breakpoints() {
+        let program = parse_instructions(EXAMPLE).unwrap();
+        let mut debugger = Debugger::new(&program);
+        assert_eq!(debugger.cont(), Stop::Loop { pc: 1 });
+        assert_eq!(debugger.accumulator(), 5);
+        assert_eq!(debugger.cont(), Stop::Loop { pc: 2 });
+        assert_eq!(debugger.steps(), 8);
+
+        debugger.reset();
+        debugger.add_breakpoint("pc == 3".parse().unwrap());
+        let acc = debugger.add_breakpoint("acc > 4".parse().unwrap());
+        assert_eq!(debugger.cont(), Stop::Breakpoint(0));
+        assert_eq!(debugger.accumulator(), 2);
+        assert_eq!(debugger.cont(), Stop::Breakpoint(acc));
+        assert_eq!(debugger.program_counter(), 4);
+    }
+
+    #[test]
+    fn commands() {
+        let program = parse_instructions(EXAMPLE).unwrap();
+        let mut debugger = Debugger::new(&program);
+        let mut run = |line: &str| debugger.execute(&line.parse().unwrap());
+        assert_eq!(run("break 4"), "breakpoint 0: pc == 4");
+        assert_eq!(run("watch acc * 10"), "watch 0: acc * 10 = 0");
+        assert_eq!(
+            run("c"),
+            "hit breakpoint 0\npc 4 acc 5 steps 6: jmp -3\nwatch 0: acc * 10 = 50"
+        );
+        assert_eq!(
+            run("list 1"),
+            "       3  acc +3\n=>*    4  jmp -3\n       5  acc -99"
+        );
+        assert!("frobnicate".parse::<Command>().is_err());
+        assert!("step x".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn going_back() {
+        let program = parse_instructions(EXAMPLE).unwrap();
+        let mut debugger = Debugger::new(&program);
+        let start = debugger.snapshot();
+        let mut run = |line: &str| debugger.execute(&line.parse().unwrap());
+        run("c");
+        assert_eq!(
+            run("back 3"),
+            "went back 3 steps\npc 7 acc 2 steps 4: jmp -4"
+        );
+        assert_eq!(
+            run("c"),
+            "about to repeat pc 1: the program loops\npc 1 acc 5 steps 7: acc +1"
+        );
+        assert_eq!(run("goto 1"), "pc 1 acc 0 steps 1: acc +1");
+        assert_eq!(
+            run("back 5"),
+            "went back 1 steps\npc 0 acc 0 steps 0: nop +0"
+        );
+        run("goto 5");
+        debugger.restore(&start);
+        assert_eq!(debugger.snapshot(), start);
+    }
+}
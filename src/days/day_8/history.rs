@@ -0,0 +1,211 @@
+use super::{Instruction, State};
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::TryFrom,
+};
+
+// A point in a run to come back to with `History::restore`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    state: State,
+    steps: usize,
+}
+
+impl Snapshot {
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+}
+
+// A run of a program which can be wound back as well as forward.  Each step
+// logs the state it replaced so that it can be undone.  Only the last
+// `capacity` steps are logged, so memory stays bounded however long the
+// run; going back further replays the program from the start, which gets
+// to the same states because nothing but the program decides them.
+pub struct History<'a> {
+    program: &'a [Instruction],
+    state: State,
+    steps: usize,
+    // The step at which each instruction first ran.
+    first_run: HashMap<i64, usize>,
+    undo: VecDeque<State>,
+    capacity: usize,
+}
+
+impl<'a> History<'a> {
+    pub fn new(program: &'a [Instruction], capacity: usize) -> Self {
+        Self {
+            program,
+            state: State::default(),
+            steps: 0,
+            first_run: HashMap::new(),
+            undo: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    // The instruction about to run, if the program counter is inside the
+    // program.
+    pub fn current(&self) -> Option<&'a Instruction> {
+        usize::try_from(self.state.program_counter)
+            .ok()
+            .and_then(|pc| self.program.get(pc))
+    }
+
+    // Whether the instruction at `pc` has run so far.
+    pub fn has_run(&self, pc: i64) -> bool {
+        self.first_run.contains_key(&pc)
+    }
+
+    // The earliest step which can be gone back to without a replay.
+    pub fn earliest(&self) -> usize {
+        self.steps - self.undo.len()
+    }
+
+    // Go back to the start of the program.
+    pub fn reset(&mut self) {
+        self.state = State::default();
+        self.steps = 0;
+        self.first_run.clear();
+        self.undo.clear();
+    }
+
+    // Run one instruction, unless the program counter is outside the
+    // program or the instruction would overflow.
+    pub fn step(&mut self) -> bool {
+        let instruction = match self.current() {
+            Some(instruction) => instruction,
+            None => return false,
+        };
+        let mut next = self.state;
+        if next.execute_instruction(instruction).is_err() {
+            return false;
+        }
+        if self.capacity > 0 {
+            if self.undo.len() == self.capacity {
+                self.undo.pop_front();
+            }
+            self.undo.push_back(self.state);
+        }
+        self.first_run
+            .entry(self.state.program_counter)
+            .or_insert(self.steps);
+        self.state = next;
+        self.steps += 1;
+        true
+    }
+
+    // Undo the last step, unless there are none.
+    pub fn step_back(&mut self) -> bool {
+        match self.steps {
+            0 => false,
+            steps => self.goto(steps - 1),
+        }
+    }
+
+    // Go to the point after `step` steps, backwards or forwards.  Going
+    // forwards stops early, returning false, if the program ends first.
+    pub fn goto(&mut self, step: usize) -> bool {
+        if step < self.earliest() {
+            self.reset();
+        }
+        while self.steps > step {
+            self.state = self.undo.pop_back().unwrap();
+            self.steps -= 1;
+            if self.first_run.get(&self.state.program_counter) == Some(&self.steps) {
+                self.first_run.remove(&self.state.program_counter);
+            }
+        }
+        while self.steps < step {
+            if !self.step() {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            state: self.state,
+            steps: self.steps,
+        }
+    }
+
+    // Go back, or forward, to where `snapshot` was taken in this run.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.goto(snapshot.steps);
+        debug_assert_eq!(self.state, snapshot.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_8::parse_instructions;
+
+    const EXAMPLE: &str = "\
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+
+    #[test]
+    fn back_and_forth() {
+        let program = parse_instructions(EXAMPLE).unwrap();
+        let mut history = History::new(&program, 3);
+        let start = history.snapshot();
+        while !history.has_run(history.state().program_counter) {
+            history.step();
+        }
+        assert_eq!(history.steps(), 7);
+        assert_eq!(history.state().accumulator, 5);
+        let looped = history.snapshot();
+
+        // Within the log.
+        assert!(history.step_back());
+        assert_eq!(history.state().program_counter, 4);
+        assert!(!history.has_run(4));
+        assert!(history.goto(5));
+        assert_eq!(history.earliest(), 4);
+
+        // Before the log, which replays the run.
+        assert!(history.goto(2));
+        assert_eq!(history.state().program_counter, 2);
+        assert_eq!(history.state().accumulator, 1);
+        assert!(!history.has_run(2));
+
+        history.restore(&looped);
+        assert_eq!(history.snapshot(), looped);
+        history.restore(&start);
+        assert!(!history.step_back());
+        assert_eq!(history.snapshot(), start);
+    }
+
+    #[test]
+    fn goto_past_the_end() {
+        let program = parse_instructions("acc +1\nacc +2\n").unwrap();
+        let mut history = History::new(&program, 0);
+        assert!(!history.goto(5));
+        assert_eq!(history.steps(), 2);
+        assert!(history.step_back());
+        assert_eq!(history.state().accumulator, 1);
+    }
+}
@@ -0,0 +1,193 @@
+use super::{analysis, disassemble, Instruction, Outcome, State};
+use std::{convert::TryFrom, fmt};
+
+// How often each instruction ran in one run of a program, and how much it
+// added to the accumulator in all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub program: Vec<Instruction>,
+    pub hits: Vec<u64>,
+    // Kept wider than the accumulator, as a loop can add to it far more
+    // than the accumulator holds while the accumulator itself stays small.
+    // Each step adds at most 2^63 either way, so even `usize::MAX` steps
+    // can't overflow an `i128`.
+    pub acc_delta: Vec<i128>,
+    pub outcome: Outcome,
+}
+
+// The cycle a run went round, as the instructions in it in the order they
+// run, starting from the lowest, and how many times each of them ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotLoop {
+    pub instructions: Vec<usize>,
+    pub iterations: u64,
+}
+
+impl Profile {
+    // Run `instructions` for at most `fuel` steps.  Unlike `execute` this
+    // doesn't stop when an instruction runs a second time, so a program
+    // which loops runs until the fuel runs out and its loop shows up hot.
+    pub fn record(instructions: &[Instruction], mut fuel: usize) -> Self {
+        let mut hits = vec![0; instructions.len()];
+        let mut acc_delta = vec![0; instructions.len()];
+        let mut state = State::default();
+
+        let outcome = loop {
+            let pc = state.program_counter;
+            if pc == instructions.len() as i64 {
+                break Outcome::Halted {
+                    acc: state.accumulator,
+                };
+            }
+            let idx = match usize::try_from(pc)
+                .ok()
+                .filter(|&idx| idx < instructions.len())
+            {
+                Some(idx) => idx,
+                None => break Outcome::OutOfBounds { pc },
+            };
+            if fuel == 0 {
+                break Outcome::FuelExhausted;
+            }
+            fuel -= 1;
+            let acc_before = state.accumulator;
+            if state.execute_instruction(&instructions[idx]).is_err() {
+                break Outcome::Overflow { pc };
+            }
+            hits[idx] += 1;
+            acc_delta[idx] += i128::from(state.accumulator) - i128::from(acc_before);
+        };
+
+        Self {
+            program: instructions.to_vec(),
+            hits,
+            acc_delta,
+            outcome,
+        }
+    }
+
+    // The loop the run went round, if it did.  Every instruction has one
+    // successor, so a run which comes back to an instruction goes round the
+    // same cycle from then on, and can only ever reach one cycle.  Its
+    // iterations are the times the run went all the way round.
+    pub fn hot_loop(&self) -> Option<HotLoop> {
+        let instructions = analysis::cycles(&self.program)
+            .into_iter()
+            .find(|cycle| self.hits[cycle[0]] > 0)?;
+        let iterations = instructions.iter().map(|&idx| self.hits[idx]).min()?;
+        Some(HotLoop {
+            instructions,
+            iterations,
+        })
+    }
+
+    // The program with each instruction's hits and accumulator delta beside
+    // it.  Instructions in the loop are marked with `|`.
+    pub fn listing(&self) -> String {
+        let hot = self.hot_loop();
+        let mut listing = format!("{:>8}  {:>8}  {:>5}  instruction\n", "hits", "acc", "pc");
+        for (idx, line) in disassemble(&self.program).lines().enumerate() {
+            let marker = match &hot {
+                Some(hot) if hot.instructions.contains(&idx) => '|',
+                _ => ' ',
+            };
+            listing.push_str(&format!(
+                "{:>8}  {:>+8}  {:>5} {} {}\n",
+                self.hits[idx], self.acc_delta[idx], idx, marker, line
+            ));
+        }
+        listing
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let steps: u64 = self.hits.iter().sum();
+        writeln!(f, "{} steps, then {}", steps, self.outcome)?;
+        match self.hot_loop() {
+            Some(hot) => {
+                let instructions: Vec<String> =
+                    hot.instructions.iter().map(|idx| idx.to_string()).collect();
+                writeln!(
+                    f,
+                    "loop {}: {} iteration(s)",
+                    instructions.join(" -> "),
+                    hot.iterations
+                )?;
+            }
+            None => writeln!(f, "no loops")?,
+        }
+        writeln!(f)?;
+        f.write_str(&self.listing())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_8::{parse_instructions, repair};
+
+    const EXAMPLE: &str = "\
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+
+    #[test]
+    fn looping_program() {
+        let program = parse_instructions(EXAMPLE).unwrap();
+        // The first instruction and then twice round the loop, which goes
+        // through both backward jumps, at 4 and 7, whose ranges overlap.
+        let profile = Profile::record(&program, 13);
+        assert_eq!(profile.outcome, Outcome::FuelExhausted);
+        assert_eq!(profile.hits, vec![1, 2, 2, 2, 2, 0, 2, 2, 0]);
+        assert_eq!(profile.acc_delta, vec![0, 2, 0, 6, 0, 0, 2, 0, 0]);
+        assert_eq!(
+            profile.hot_loop(),
+            Some(HotLoop {
+                instructions: vec![1, 2, 6, 7, 3, 4],
+                iterations: 2
+            })
+        );
+        assert!(profile.to_string().starts_with(
+            "13 steps, then ran out of fuel\nloop 1 -> 2 -> 6 -> 7 -> 3 -> 4: 2 iteration(s)\n"
+        ));
+        let listing = profile.listing();
+        assert!(listing.contains("       2        +6      3 | acc +3\n"));
+        assert!(listing.contains("       0        +0      5   acc -99\n"));
+        assert!(listing.contains("       0        +0      8   acc +6\n"));
+    }
+
+    #[test]
+    fn large_arguments() {
+        // The accumulator goes up and back down each time round, while
+        // the total each `acc` adds grows past what an `i64` holds.
+        let program =
+            parse_instructions("acc +9223372036854775807\nacc -9223372036854775807\njmp -2\n")
+                .unwrap();
+        let profile = Profile::record(&program, 30);
+        assert_eq!(profile.outcome, Outcome::FuelExhausted);
+        assert_eq!(profile.acc_delta[0], 10 * i128::from(i64::MAX));
+        assert_eq!(profile.acc_delta[1], -10 * i128::from(i64::MAX));
+        assert!(profile.listing().contains("+92233720368547758070"));
+    }
+
+    #[test]
+    fn repaired_program() {
+        let program = parse_instructions(EXAMPLE).unwrap();
+        let patched = repair(&program).unwrap().apply(&program);
+        let profile = Profile::record(&patched, 100);
+        assert_eq!(profile.outcome, Outcome::Halted { acc: 8 });
+        assert!(profile.hits.iter().all(|&hits| hits <= 1));
+        assert_eq!(profile.hot_loop(), None);
+        assert!(profile
+            .to_string()
+            .starts_with("6 steps, then halted with acc 8\nno loops\n"));
+    }
+}
@@ -0,0 +1,104 @@
+use super::{analysis, Instruction, State};
+use std::collections::HashSet;
+
+// The single `jmp`/`nop` flip which makes a program terminate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repair {
+    pub index: usize,
+    pub accumulator: i64,
+}
+
+impl Repair {
+    // `instructions` with the fix applied.
+    pub fn apply(&self, instructions: &[Instruction]) -> Vec<Instruction> {
+        let mut patched = instructions.to_vec();
+        if let Some(flipped) = patched[self.index].flipped() {
+            patched[self.index] = flipped;
+        }
+        patched
+    }
+}
+
+// Find the flip in linear time.  Every instruction has exactly one
+// successor, so the instructions from which the unpatched program
+// terminates are found by walking the control-flow graph backwards from
+// the end.  The fix is the instruction on the program's looping path
+// whose flipped successor is one of those.
+//
+// A flip can't lead back to itself: if the flipped successor's path
+// passed through the flipped instruction, that instruction would
+// terminate unpatched and so would the program.  Returns `None` if the
+// program already terminates or no single flip fixes it.
+pub fn repair(instructions: &[Instruction]) -> Option<Repair> {
+    let terminates = analysis::terminates(instructions);
+    if terminates[0] {
+        return None;
+    }
+
+    let mut visited = HashSet::new();
+    let mut idx = 0;
+    while let Some(&instruction) = instructions.get(idx) {
+        if !visited.insert(idx) {
+            return None;
+        }
+        if let Some(flipped) = instruction.flipped() {
+            if analysis::target(instructions, flipped.successor(idx))
+                .is_some_and(|target| terminates[target])
+            {
+                return Some(Repair {
+                    index: idx,
+                    accumulator: run_patched(instructions, idx, flipped)?,
+                });
+            }
+        }
+        idx = analysis::target(instructions, instruction.successor(idx))?;
+    }
+    None
+}
+
+// The final accumulator of a program which is known to terminate once
+// the instruction at `patched` is replaced by `replacement`, unless it
+// overflows on the way.
+fn run_patched(
+    instructions: &[Instruction],
+    patched: usize,
+    replacement: Instruction,
+) -> Option<i64> {
+    let mut state = State::default();
+    while let Some(&instruction) = instructions.get(state.program_counter as usize) {
+        let instruction = if state.program_counter as usize == patched {
+            replacement
+        } else {
+            instruction
+        };
+        state.execute_instruction(&instruction).ok()?;
+    }
+    Some(state.accumulator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_8::{assemble, parse_instructions, repair_by_brute_force};
+
+    #[test]
+    fn matches_brute_force() {
+        for input in &[
+            "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n",
+            include_str!("../../../data/day_8.txt"),
+        ] {
+            let program = parse_instructions(input).unwrap();
+            let repair = repair(&program).unwrap();
+            assert_eq!(Some(repair.accumulator), repair_by_brute_force(&program));
+            assert!(program[repair.index].flipped().is_some());
+        }
+    }
+
+    #[test]
+    fn nothing_to_repair() {
+        let terminates = assemble("acc +1\njmp end\nacc +2\nend:\n").unwrap();
+        assert_eq!(repair(&terminates), None);
+        let unfixable = parse_instructions("jmp +2\njmp +1\njmp -1\njmp +0\n").unwrap();
+        assert_eq!(repair(&unfixable), None);
+    }
+}
@@ -0,0 +1,308 @@
+use super::{execute_with, Instruction, Operation, Outcome};
+use std::collections::HashSet;
+
+// One changed instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Patch {
+    pub index: usize,
+    pub replacement: Instruction,
+}
+
+// Changes which together make a program terminate, and the accumulator
+// it then terminates with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchSet {
+    pub patches: Vec<Patch>,
+    pub accumulator: i64,
+}
+
+// What `search` may change.
+#[derive(Debug, Clone)]
+pub struct Search {
+    pub max_changes: usize,
+    // The arguments an `acc` may be changed to.  This can't change where
+    // the program goes, only the accumulator it terminates with, so these
+    // edits are only tried on the `acc`s a terminating program runs.
+    pub acc_arguments: Vec<i64>,
+    // Also allow replacing an `acc` with a `jmp` or `nop` with the same
+    // argument.
+    pub replace_acc: bool,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Self {
+            max_changes: 1,
+            acc_arguments: vec![],
+            replace_acc: false,
+        }
+    }
+}
+
+impl Search {
+    fn replacements(&self, instruction: Instruction) -> Vec<Instruction> {
+        match instruction {
+            Instruction(Operation::Jmp, arg) => vec![Instruction(Operation::Nop, arg)],
+            Instruction(Operation::Nop, arg) => vec![Instruction(Operation::Jmp, arg)],
+            Instruction(Operation::Acc, arg) if self.replace_acc => vec![
+                Instruction(Operation::Jmp, arg),
+                Instruction(Operation::Nop, arg),
+            ],
+            Instruction(Operation::Acc, _) => vec![],
+        }
+    }
+}
+
+// Find every minimal set of up to `search.max_changes` changes which makes
+// the program terminate, fewest changes first.  A set is minimal if no
+// smaller set found with the same accumulator is part of it; without
+// `acc` edits, the unchanged program is the only one if it already
+// terminates.
+//
+// Only changes to instructions the program runs can matter, so the search
+// patches each of those in turn, runs the patched program and carries on
+// from there.  Every minimal set is found this way: ordered by when the
+// patched program reaches them, each change is on the path of the program
+// with only the changes before it.
+pub fn search(instructions: &[Instruction], search: &Search) -> Vec<PatchSet> {
+    let mut found = vec![];
+    let mut seen = HashSet::new();
+    explore(
+        &mut instructions.to_vec(),
+        &mut vec![],
+        search,
+        &mut seen,
+        &mut found,
+    );
+    if !search.acc_arguments.is_empty() {
+        for set in found.clone() {
+            edit_acc_arguments(instructions, &set, search, &mut found);
+        }
+    }
+
+    found.sort_by(|a: &PatchSet, b| {
+        (a.patches.len(), &a.patches).cmp(&(b.patches.len(), &b.patches))
+    });
+    let mut minimal: Vec<PatchSet> = vec![];
+    for set in found {
+        let is_superset = minimal.iter().any(|smaller| {
+            smaller.accumulator == set.accumulator
+                && smaller.patches.iter().all(|p| set.patches.contains(p))
+        });
+        if !is_superset {
+            minimal.push(set);
+        }
+    }
+    minimal
+}
+
+fn explore(
+    program: &mut Vec<Instruction>,
+    patches: &mut Vec<Patch>,
+    search: &Search,
+    seen: &mut HashSet<Vec<Patch>>,
+    found: &mut Vec<PatchSet>,
+) {
+    let mut key = patches.clone();
+    key.sort();
+    if !seen.insert(key.clone()) {
+        return;
+    }
+
+    let mut path = vec![];
+    match execute_with(program, usize::MAX, |step| path.push(step.pc as usize)) {
+        Outcome::Halted { acc } => {
+            found.push(PatchSet {
+                patches: key,
+                accumulator: acc,
+            });
+            return;
+        }
+        _ if patches.len() == search.max_changes => return,
+        _ => {}
+    }
+
+    for idx in path {
+        if patches.iter().any(|patch| patch.index == idx) {
+            continue;
+        }
+        let original = program[idx];
+        for replacement in search.replacements(original) {
+            program[idx] = replacement;
+            patches.push(Patch {
+                index: idx,
+                replacement,
+            });
+            explore(program, patches, search, seen, found);
+            patches.pop();
+        }
+        program[idx] = original;
+    }
+}
+
+// Add to `found` each way of changing the arguments of the `acc`s which
+// `set` runs, keeping within `search.max_changes`.  The program
+// terminates, so each of them runs once and the accumulator moves by
+// however much its argument does.
+fn edit_acc_arguments(
+    instructions: &[Instruction],
+    set: &PatchSet,
+    search: &Search,
+    found: &mut Vec<PatchSet>,
+) {
+    let mut program = instructions.to_vec();
+    for patch in &set.patches {
+        program[patch.index] = patch.replacement;
+    }
+    let mut accs = vec![];
+    execute_with(&program, usize::MAX, |step| {
+        if let Instruction(Operation::Acc, arg) = step.instruction {
+            if set
+                .patches
+                .iter()
+                .all(|patch| patch.index != step.pc as usize)
+            {
+                accs.push((step.pc as usize, arg));
+            }
+        }
+    });
+
+    // Each choice of `acc`s in the order they run, and of a new argument
+    // for each.
+    let mut stack = vec![(0, set.clone())];
+    while let Some((next, set)) = stack.pop() {
+        if set.patches.len() == search.max_changes {
+            continue;
+        }
+        for (position, &(index, arg)) in accs.iter().enumerate().skip(next) {
+            for &replacement in &search.acc_arguments {
+                if replacement == arg {
+                    continue;
+                }
+                // Leave out edits whose total the accumulator can't hold.
+                let accumulator = match set
+                    .accumulator
+                    .checked_sub(arg)
+                    .and_then(|accumulator| accumulator.checked_add(replacement))
+                {
+                    Some(accumulator) => accumulator,
+                    None => continue,
+                };
+                let mut patches = set.patches.clone();
+                patches.push(Patch {
+                    index,
+                    replacement: Instruction(Operation::Acc, replacement),
+                });
+                patches.sort();
+                let edited = PatchSet {
+                    patches,
+                    accumulator,
+                };
+                found.push(edited.clone());
+                stack.push((position + 1, edited));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_8::{parse_instructions, repair, repair_by_brute_force};
+
+    #[test]
+    fn single_flips_match_repair() {
+        let program = parse_instructions(include_str!("../../../data/day_8.txt")).unwrap();
+        let found = search(&program, &Search::default());
+        let repair = repair(&program).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].patches[0].index, repair.index);
+        assert_eq!(Some(found[0].accumulator), repair_by_brute_force(&program));
+    }
+
+    #[test]
+    fn minimal_sets() {
+        // Needs both `jmp`s at 1 and 3 flipped, or the one at 1 flipped and
+        // the `acc` at 2 made into a jump past the one at 3.
+        let program = parse_instructions("nop +0\njmp +0\nacc +2\njmp -3\nacc +1\n").unwrap();
+        assert!(search(&program, &Search::default()).is_empty());
+
+        let search_two = Search {
+            max_changes: 2,
+            ..Search::default()
+        };
+        let found = search(&program, &search_two);
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].patches,
+            vec![
+                Patch {
+                    index: 1,
+                    replacement: Instruction(Operation::Nop, 0)
+                },
+                Patch {
+                    index: 3,
+                    replacement: Instruction(Operation::Nop, -3)
+                },
+            ]
+        );
+        assert_eq!(found[0].accumulator, 3);
+
+        let replace_acc = Search {
+            replace_acc: true,
+            ..search_two.clone()
+        };
+        let found = search(&program, &replace_acc);
+        let changes: Vec<usize> = found.iter().map(|set| set.patches.len()).collect();
+        assert_eq!(changes, vec![2, 2]);
+        assert_eq!(found[0].accumulator, 1);
+        assert_eq!(found[1].accumulator, 3);
+
+        let terminates = parse_instructions("acc +1\n").unwrap();
+        let found = search(&terminates, &search_two);
+        assert_eq!(
+            found,
+            vec![PatchSet {
+                patches: vec![],
+                accumulator: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn acc_arguments() {
+        // Terminates as it is, through the `acc`s at 0 and 3.
+        let program = parse_instructions("acc +1\njmp +2\nacc +5\nacc +2\n").unwrap();
+        let search = Search {
+            max_changes: 2,
+            acc_arguments: vec![2, 10],
+            ..Search::default()
+        };
+        let found = search_for(&program, &search);
+        assert_eq!(
+            found,
+            vec![
+                (vec![], 3),
+                (vec![(0, 2)], 4),
+                (vec![(0, 10)], 12),
+                (vec![(3, 10)], 11),
+                (vec![(0, 2), (3, 10)], 12),
+                (vec![(0, 10), (3, 10)], 20),
+            ]
+        );
+    }
+
+    fn search_for(program: &[Instruction], search: &Search) -> Vec<(Vec<(usize, i64)>, i64)> {
+        super::search(program, search)
+            .into_iter()
+            .map(|set| {
+                let patches = set
+                    .patches
+                    .iter()
+                    .map(|patch| (patch.index, patch.replacement.1))
+                    .collect();
+                (patches, set.accumulator)
+            })
+            .collect()
+    }
+}
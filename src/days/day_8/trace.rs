@@ -0,0 +1,242 @@
+use super::{execute_with, Instruction, Outcome, Step};
+use crate::{
+    error::{Error, ParseError, Result},
+    parse,
+};
+use std::{fmt, fs, path::Path};
+
+// Every step of one run of a program and how the run ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub steps: Vec<Step>,
+    pub outcome: Outcome,
+}
+
+impl Trace {
+    pub fn record(instructions: &[Instruction], fuel: usize) -> Self {
+        let mut steps = vec![];
+        let outcome = execute_with(instructions, fuel, |step| steps.push(*step));
+        Self { steps, outcome }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).map_err(|source| Error::Input {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&contents).map_err(|err| Error::from(err).in_file(path))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string()).map_err(|source| Error::Output {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    // Read the format written by `Display`: a `pc instruction acc` line
+    // per step, with the accumulator after the step, and then an `end`
+    // line with the outcome.
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let mut steps = vec![];
+        let mut outcome = None;
+        parse::lines(contents, |line| {
+            if line.starts_with('#') {
+                return Ok(());
+            }
+            if outcome.is_some() {
+                return Err(ParseError::new(line, 1, "unexpected line after `end`"));
+            }
+            let fields: Vec<&str> = line.split(' ').collect();
+            if fields[0] == "end" {
+                outcome = Some(parse_outcome(line, &fields[1..])?);
+                return Ok(());
+            }
+            if fields.len() != 4 {
+                return Err(ParseError::new(line, 1, "expected `pc instruction acc`"));
+            }
+            let argument: i64 = parse::field(line, fields[2], "invalid argument")?;
+            steps.push(Step {
+                pc: parse::field(line, fields[0], "invalid pc")?,
+                instruction: Instruction(
+                    parse::field(line, fields[1], "invalid operation")?,
+                    argument,
+                ),
+                acc_before: steps.last().map_or(0, |step: &Step| step.acc_after),
+                acc_after: parse::field(line, fields[3], "invalid accumulator")?,
+            });
+            Ok(())
+        })?;
+
+        let outcome = outcome.ok_or_else(|| ParseError::new("", 1, "the trace has no `end`"))?;
+        Ok(Self { steps, outcome })
+    }
+
+    // The trace step by step, for reading.
+    pub fn listing(&self) -> String {
+        let mut listing: String = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(idx, step)| format!("{:>6}  {}\n", idx, describe(step)))
+            .collect();
+        listing.push_str(&format!("{:>6}  {}\n", self.steps.len(), self.outcome));
+        listing
+    }
+
+    pub fn diff<'a>(&'a self, other: &'a Trace) -> Diff<'a> {
+        let common = self
+            .steps
+            .iter()
+            .zip(&other.steps)
+            .take_while(|(a, b)| a == b)
+            .count();
+        Diff {
+            common,
+            left: self,
+            right: other,
+        }
+    }
+}
+
+fn describe(step: &Step) -> String {
+    format!(
+        "pc {:>4}  {:<8}  acc {} -> {}",
+        step.pc,
+        step.instruction.to_string(),
+        step.acc_before,
+        step.acc_after
+    )
+}
+
+fn parse_outcome(line: &str, fields: &[&str]) -> Result<Outcome, ParseError> {
+    let number = |idx: usize| match fields.get(idx) {
+        Some(field) => parse::field(line, field, "invalid number"),
+        None => Err(ParseError::new(line, 1, "missing number")),
+    };
+    match fields.first() {
+        Some(&"halted") => Ok(Outcome::Halted { acc: number(1)? }),
+        Some(&"loop") => Ok(Outcome::InfiniteLoop {
+            pc: number(1)?,
+            acc: number(2)?,
+        }),
+        Some(&"out-of-bounds") => Ok(Outcome::OutOfBounds { pc: number(1)? }),
+        Some(&"overflow") => Ok(Outcome::Overflow { pc: number(1)? }),
+        Some(&"fuel") => Ok(Outcome::FuelExhausted),
+        _ => Err(ParseError::new(
+            line,
+            1,
+            "expected `end halted`, `end loop`, `end out-of-bounds`, `end overflow` or `end fuel`",
+        )),
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# pc instruction acc")?;
+        for step in &self.steps {
+            writeln!(f, "{} {} {}", step.pc, step.instruction, step.acc_after)?;
+        }
+        match self.outcome {
+            Outcome::Halted { acc } => writeln!(f, "end halted {}", acc),
+            Outcome::InfiniteLoop { pc, acc } => writeln!(f, "end loop {} {}", pc, acc),
+            Outcome::OutOfBounds { pc } => writeln!(f, "end out-of-bounds {}", pc),
+            Outcome::Overflow { pc } => writeln!(f, "end overflow {}", pc),
+            Outcome::FuelExhausted => writeln!(f, "end fuel"),
+        }
+    }
+}
+
+// Where two traces part ways.
+pub struct Diff<'a> {
+    // The number of steps at the start which are the same.
+    pub common: usize,
+    pub left: &'a Trace,
+    pub right: &'a Trace,
+}
+
+impl Diff<'_> {
+    pub fn is_same(&self) -> bool {
+        self.left == self.right
+    }
+}
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_same() {
+            return write!(
+                f,
+                "the traces are the same: {} steps, then {}",
+                self.common, self.left.outcome
+            );
+        }
+        writeln!(f, "the traces agree for {} steps", self.common)?;
+        for (side, trace) in &[("left", self.left), ("right", self.right)] {
+            match trace.steps.get(self.common) {
+                Some(step) => writeln!(
+                    f,
+                    "{:>5}: {}, then {} more steps and {}",
+                    side,
+                    describe(step),
+                    trace.steps.len() - self.common - 1,
+                    trace.outcome
+                )?,
+                None => writeln!(f, "{:>5}: {}", side, trace.outcome)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_8::{parse_instructions, repair};
+
+    const EXAMPLE: &str = "\
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+
+    #[test]
+    fn save_and_load() {
+        let program = parse_instructions(EXAMPLE).unwrap();
+        let trace = Trace::record(&program, usize::MAX);
+        assert_eq!(trace.steps.len(), 7);
+        assert_eq!(trace.outcome, Outcome::InfiniteLoop { pc: 1, acc: 5 });
+        assert!(trace
+            .to_string()
+            .starts_with("# pc instruction acc\n0 nop +0 0\n1 acc +1 1\n"));
+        assert_eq!(Trace::parse(&trace.to_string()).unwrap(), trace);
+
+        let err = Trace::parse("0 nop +0 0\n1 mul +2 0\nend fuel\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert!(Trace::parse("0 nop +0 0\n").is_err());
+    }
+
+    #[test]
+    fn diff_with_repair() {
+        let program = parse_instructions(EXAMPLE).unwrap();
+        let patched = repair(&program).unwrap().apply(&program);
+        let original = Trace::record(&program, usize::MAX);
+        let repaired = Trace::record(&patched, usize::MAX);
+
+        let diff = original.diff(&repaired);
+        assert_eq!(diff.common, 4);
+        assert_eq!(
+            diff.to_string(),
+            "the traces agree for 4 steps
+ left: pc    7  jmp -4    acc 2 -> 2, then 2 more steps and looped at pc 1 with acc 5
+right: pc    7  nop -4    acc 2 -> 2, then 1 more steps and halted with acc 8
+"
+        );
+        assert!(original.diff(&original).is_same());
+    }
+}
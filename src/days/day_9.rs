@@ -0,0 +1,134 @@
+use crate::{
+    error::{ParseError, Result},
+    parse, Solution,
+};
+use std::io::BufRead;
+
+const PREAMBLE_LENGTH: usize = 25;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input, parse_number)?)
+    }
+
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input> {
+        parse::read_lines(reader, parse_number)
+    }
+
+    fn part1(numbers: &Self::Input) -> Option<usize> {
+        part1(numbers, PREAMBLE_LENGTH)
+    }
+
+    fn part2(numbers: &Self::Input) -> Option<usize> {
+        part2(numbers, part1(numbers, PREAMBLE_LENGTH)?)
+    }
+}
+
+fn parse_number(line: &str) -> Result<usize, ParseError> {
+    parse::field(line, line, "invalid number")
+}
+
+fn is_sum_of_any_pair(target: usize, numbers: &[usize]) -> bool {
+    for (i, n1) in numbers.iter().enumerate() {
+        for n2 in &numbers[(i + 1)..] {
+            if n1 + n2 == target {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+fn part1(numbers: &[usize], preamble_length: usize) -> Option<usize> {
+    for i in preamble_length..numbers.len() {
+        let target = numbers[i];
+        let preceding_numbers = &numbers[(i - preamble_length)..i];
+        if !is_sum_of_any_pair(target, preceding_numbers) {
+            return Some(target);
+        }
+    }
+
+    None
+}
+
+fn part2(numbers: &[usize], target: usize) -> Option<usize> {
+    let slice = find_slice_which_sums_to_target(numbers, target)?;
+    let min = slice.iter().min()?;
+    let max = slice.iter().max()?;
+    Some(min + max)
+}
+
+fn find_slice_which_sums_to_target(numbers: &[usize], target: usize) -> Option<&[usize]> {
+    for i in 0..numbers.len() {
+        for slice_size in 2..=(numbers.len() - i) {
+            let slice = &numbers[i..(i + slice_size)];
+            let sum: usize = slice.iter().sum();
+            if sum == target {
+                return Some(slice);
+            }
+            if sum > target {
+                break;
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576
+";
+
+    #[test]
+    fn example() {
+        let numbers = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&numbers, 5), Some(127));
+        assert_eq!(part2(&numbers, 127), Some(62));
+    }
+
+    #[test]
+    fn no_answer() {
+        // Too short to get past the preamble.
+        let numbers = Day9::parse("1\n2\n").unwrap();
+        assert_eq!(Day9::part1(&numbers), None);
+        assert_eq!(Day9::part2(&numbers), None);
+        assert_eq!(part2(&[1, 2, 3], 100), None);
+    }
+
+    #[test]
+    fn input() {
+        let numbers = Day9::parse(include_str!("../../data/day_9.txt")).unwrap();
+        assert_eq!(Day9::part1(&numbers), Some(15690279));
+        assert_eq!(Day9::part2(&numbers), Some(2174232));
+    }
+}
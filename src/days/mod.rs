@@ -0,0 +1,33 @@
+use crate::runner::Day;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+pub const ALL: &[Day] = &[
+    Day::new::<day_1::Day1>(1),
+    Day::new::<day_2::Day2>(2),
+    Day::new::<day_3::Day3>(3),
+    Day::new::<day_4::Day4>(4),
+    Day::new::<day_5::Day5>(5),
+    Day::new::<day_6::Day6>(6),
+    Day::new::<day_7::Day7>(7),
+    Day::new::<day_8::Day8>(8),
+    Day::new::<day_9::Day9>(9),
+    Day::new::<day_10::Day10>(10),
+    Day::new::<day_11::Day11>(11),
+    Day::new::<day_12::Day12>(12),
+    Day::new::<day_13::Day13>(13),
+    Day::new::<day_14::Day14>(14),
+];
@@ -0,0 +1,49 @@
+use std::cell::RefCell;
+
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+// Diagnostic output from a solution.  It is kept apart from the answers:
+// the runner collects it while a part runs so that it can be reported
+// separately, and anywhere else (tests, benchmarks) it is discarded.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::debug::emit(format!($($arg)*))
+    };
+}
+
+pub fn emit(line: String) {
+    CAPTURED.with(|captured| {
+        if let Some(lines) = captured.borrow_mut().as_mut() {
+            lines.push(line);
+        }
+    })
+}
+
+// Run `f`, returning its result along with everything it passed to
+// `debug!`.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let previous = CAPTURED.with(|captured| captured.replace(Some(vec![])));
+    let result = f();
+    let lines = CAPTURED.with(|captured| captured.replace(previous));
+    (result, lines.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_nested_output() {
+        debug!("discarded");
+        let ((), outer) = capture(|| {
+            debug!("outer {}", 1);
+            let ((), inner) = capture(|| debug!("inner"));
+            assert_eq!(inner, vec!["inner"]);
+            debug!("outer {}", 2);
+        });
+        assert_eq!(outer, vec!["outer 1", "outer 2"]);
+    }
+}
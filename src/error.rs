@@ -0,0 +1,128 @@
+use crate::input;
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    // Reading the input failed part way through.  `in_file` turns this
+    // into an `Input` error once the path is known.
+    Io(io::Error),
+    Input { path: PathBuf, source: io::Error },
+    Output { path: PathBuf, source: io::Error },
+    Exists(PathBuf),
+    Parse(ParseError),
+}
+
+impl Error {
+    // Parsers only see the input text, so the file name is filled in by
+    // whoever read the file.
+    pub fn in_file(self, path: &Path) -> Self {
+        match self {
+            Error::Io(source) => Error::Input {
+                path: path.to_path_buf(),
+                source,
+            },
+            Error::Parse(err) => Error::Parse(err.in_file(path)),
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(source) => write!(f, "could not read input: {}", source),
+            Error::Input { path, source } => {
+                write!(f, "could not read input {}: {}", input::name(path), source)
+            }
+            Error::Output { path, source } => {
+                write!(f, "could not write {}: {}", path.display(), source)
+            }
+            Error::Exists(path) => write!(f, "{} already exists", path.display()),
+            Error::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(source) | Error::Input { source, .. } | Error::Output { source, .. } => {
+                Some(source)
+            }
+            Error::Exists(_) | Error::Parse(_) => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+// A problem with the puzzle input.  `line` and `column` are 1-based and
+// `text` is the full line containing the problem, so the error can be
+// displayed with a marker under the offending character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // An error in a single line of input.  Line-oriented parsers report
+    // errors relative to the line they were given and `parse::lines`
+    // renumbers them with `on_line`.
+    pub fn new(text: &str, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            file: None,
+            line: 1,
+            column,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn on_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+
+    pub fn in_file(self, path: &Path) -> Self {
+        Self {
+            file: Some(path.to_path_buf()),
+            ..self
+        }
+    }
+
+    pub fn from_peg(input: &str, err: peg::error::ParseError<peg::str::LineCol>) -> Self {
+        let text = input.lines().nth(err.location.line - 1).unwrap_or("");
+        Self::new(
+            text,
+            err.location.column,
+            format!("expected {}", err.expected),
+        )
+        .on_line(err.location.line)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:", input::name(file))?,
+            None => write!(f, "line ")?,
+        }
+        writeln!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
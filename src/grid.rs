@@ -0,0 +1,262 @@
+use crate::{error::ParseError, parse};
+use std::ops::{Add, Index, IndexMut, Mul};
+
+// A cell on a grid.  `y` grows downwards, the same way the rows of a
+// puzzle input do.  Positions are signed so that stepping off the edge of
+// a grid gives a position which is simply not on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Position {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Offset {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Offset {
+    pub const fn new(dx: isize, dy: isize) -> Self {
+        Self { dx, dy }
+    }
+
+    // The eight directions to the cells surrounding a cell, row by row.
+    pub const NEIGHBORS: [Offset; 8] = [
+        Offset::new(-1, -1),
+        Offset::new(0, -1),
+        Offset::new(1, -1),
+        Offset::new(-1, 0),
+        Offset::new(1, 0),
+        Offset::new(-1, 1),
+        Offset::new(0, 1),
+        Offset::new(1, 1),
+    ];
+}
+
+impl Add<Offset> for Position {
+    type Output = Position;
+
+    fn add(self, rhs: Offset) -> Self::Output {
+        Position::new(self.x + rhs.dx, self.y + rhs.dy)
+    }
+}
+
+impl Mul<Offset> for isize {
+    type Output = Offset;
+
+    fn mul(self, rhs: Offset) -> Self::Output {
+        Offset::new(self * rhs.dx, self * rhs.dy)
+    }
+}
+
+// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // Parse one row per line, turning each character into a cell with
+    // `cell`.  `expected` describes the valid characters for when `cell`
+    // returns `None`.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let rows = parse::lines(input, |line| {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(idx, chr)| cell(chr).ok_or_else(|| ParseError::new(line, idx + 1, expected)))
+                .collect::<Result<Vec<T>, ParseError>>()?;
+            match width {
+                Some(width) if width != row.len() => Err(ParseError::new(
+                    line,
+                    1,
+                    format!("expected {} cells, found {}", width, row.len()),
+                )),
+                _ => {
+                    width = Some(row.len());
+                    Ok(row)
+                }
+            }
+        })?;
+        let width = width.ok_or_else(|| ParseError::new("", 1, "the grid is empty"))?;
+        let height = rows.len();
+
+        Ok(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        (0..self.width as isize).contains(&pos.x) && (0..self.height as isize).contains(&pos.y)
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        self.index_of(pos).map(move |idx| &mut self.cells[idx])
+    }
+
+    // The cell at `pos` on a grid which repeats forever in every
+    // direction.
+    pub fn get_wrapping(&self, pos: Position) -> &T {
+        let x = pos.x.rem_euclid(self.width as isize);
+        let y = pos.y.rem_euclid(self.height as isize);
+        &self[Position::new(x, y)]
+    }
+
+    // Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| (self.position_of(idx), cell))
+    }
+
+    // The cells surrounding `pos`, including diagonally, which are on the
+    // grid.
+    pub fn neighbors(&self, pos: Position) -> impl Iterator<Item = (Position, &T)> {
+        Offset::NEIGHBORS.iter().filter_map(move |&offset| {
+            let neighbor = pos + offset;
+            self.get(neighbor).map(|cell| (neighbor, cell))
+        })
+    }
+
+    // The cells passed by a ray from `pos` (not included) in `direction`,
+    // until it leaves the grid.
+    pub fn cast(&self, pos: Position, direction: Offset) -> impl Iterator<Item = (Position, &T)> {
+        (1..).map_while(move |distance| {
+            let cell = pos + distance * direction;
+            self.get(cell).map(|value| (cell, value))
+        })
+    }
+
+    // A grid of the same shape with each cell replaced by `f` of its
+    // position and value.
+    pub fn map<U>(&self, mut f: impl FnMut(Position, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(pos, cell)| f(pos, cell)).collect(),
+        }
+    }
+
+    // Draw the grid as text, one line per row, the reverse of `parse`.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width) {
+            text.extend(row.iter().map(&mut cell));
+            text.push('\n');
+        }
+        text
+    }
+
+    fn index_of(&self, pos: Position) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.y as usize * self.width + pos.x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn position_of(&self, idx: usize) -> Position {
+        Position::new((idx % self.width) as isize, (idx / self.width) as isize)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside a {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside a {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, |chr| chr.to_digit(10), "expected a digit").unwrap()
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = digits("123\n456\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Position::new(2, 1)], 6);
+        assert_eq!(
+            grid.render(|&d| std::char::from_digit(d, 10).unwrap()),
+            "123\n456\n"
+        );
+
+        let err = Grid::parse("12\n1x\n", |chr| chr.to_digit(10), "expected a digit").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = Grid::parse("12\n123\n", |chr| chr.to_digit(10), "expected a digit").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn access() {
+        let grid = digits("123\n456\n");
+        assert_eq!(grid.get(Position::new(-1, 0)), None);
+        assert_eq!(grid.get(Position::new(3, 0)), None);
+        assert_eq!(*grid.get_wrapping(Position::new(3, 0)), 1);
+        assert_eq!(*grid.get_wrapping(Position::new(-1, -1)), 6);
+    }
+
+    #[test]
+    fn neighbors_and_rays() {
+        let grid = digits("123\n456\n789\n");
+        let corner: Vec<u32> = grid
+            .neighbors(Position::new(0, 0))
+            .map(|(_, &d)| d)
+            .collect();
+        assert_eq!(corner, vec![2, 4, 5]);
+        assert_eq!(grid.neighbors(Position::new(1, 1)).count(), 8);
+
+        let ray: Vec<u32> = grid
+            .cast(Position::new(0, 0), Offset::new(1, 1))
+            .map(|(_, &d)| d)
+            .collect();
+        assert_eq!(ray, vec![5, 9]);
+    }
+}
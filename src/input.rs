@@ -0,0 +1,60 @@
+use crate::error::{Error, Result};
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+// Overrides the input file when a single day is run, e.g.
+// `AOC_INPUT=example.txt aoc 7`.
+pub const INPUT_VAR: &str = "AOC_INPUT";
+
+// The input path which reads from standard input instead of a file.
+pub const STDIN: &str = "-";
+
+pub fn default_path(day: u32) -> PathBuf {
+    PathBuf::from(format!("data/day_{}.txt", day))
+}
+
+// Pick the input file for `day`: an explicit path wins over the
+// environment, which wins over the file in `data/`.
+pub fn resolve(day: u32, explicit: Option<&Path>) -> PathBuf {
+    explicit
+        .map(PathBuf::from)
+        .or_else(|| env::var_os(INPUT_VAR).map(PathBuf::from))
+        .unwrap_or_else(|| default_path(day))
+}
+
+pub fn open(path: &Path) -> Result<Box<dyn BufRead>> {
+    if path == Path::new(STDIN) {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    match File::open(path) {
+        Ok(file) => Ok(Box::new(BufReader::new(file))),
+        Err(source) => Err(Error::Input {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+pub fn read(path: &Path) -> Result<String> {
+    let mut input = String::new();
+    open(path)?
+        .read_to_string(&mut input)
+        .map_err(|source| Error::Input {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    Ok(input)
+}
+
+// How to refer to `path` in messages.
+pub fn name(path: &Path) -> String {
+    if path == Path::new(STDIN) {
+        String::from("<stdin>")
+    } else {
+        path.display().to_string()
+    }
+}
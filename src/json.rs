@@ -0,0 +1,37 @@
+use std::fmt::Write;
+
+// Quote `s` as a JSON string.
+pub fn string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for chr in s.chars() {
+        match chr {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+pub fn array<T>(items: &[T], item: impl Fn(&T) -> String) -> String {
+    let items: Vec<String> = items.iter().map(item).collect();
+    format!("[{}]", items.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(string("plain"), r#""plain""#);
+        assert_eq!(string("a \"b\"\\\n\u{1}"), r#""a \"b\"\\\n\u0001""#);
+        assert_eq!(array(&["x", "y"], |s| string(s)), r#"["x","y"]"#);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{
+    error::{Error, ParseError, Result},
+    parse, Part,
+};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, BufRead, Read},
+    path::Path,
+};
+
+pub const DEFAULT_PATH: &str = "data/ledger.txt";
+
+// A stable hash of a puzzle input, so that answers are only compared
+// against the input they were confirmed for.  This is 64-bit FNV-1a,
+// which unlike `DefaultHasher` is the same across Rust releases.
+pub fn hash(input: &str) -> u64 {
+    let mut hasher = Hasher::default();
+    hasher.write(input.as_bytes());
+    hasher.finish()
+}
+
+// `hash`, computed a piece at a time.
+#[derive(Debug, Clone, Copy)]
+pub struct Hasher(u64);
+
+impl Default for Hasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher {
+    pub fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+// Hashes an input as it is read, so that input which can only be read
+// once, like stdin, can still be checked against the ledger.
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Hasher,
+}
+
+impl<R: BufRead> HashingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Hasher::default(),
+        }
+    }
+
+    // The hash of the whole input.  Whatever has not been read yet is
+    // read and discarded first.
+    pub fn finish(mut self) -> io::Result<u64> {
+        io::copy(&mut self, &mut io::sink())?;
+        Ok(self.hasher.finish())
+    }
+}
+
+impl<R: BufRead> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.hasher.write(&buf[..len]);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for HashingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // Nothing has been consumed since the caller's `fill_buf`, so this
+        // returns the same buffer without reading any more.
+        if amt > 0 {
+            if let Ok(buf) = self.inner.fill_buf() {
+                self.hasher.write(&buf[..amt.min(buf.len())]);
+            }
+        }
+        self.inner.consume(amt);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Correct => f.write_str("correct"),
+            Status::Wrong { expected } => write!(f, "wrong, expected {}", expected),
+            Status::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+// Answers which have been accepted for a given input, stored one per
+// line as `day part input-hash answer`.
+#[derive(Debug, Default)]
+pub struct Ledger {
+    answers: BTreeMap<(u32, u32, u64), String>,
+}
+
+impl Ledger {
+    // Load the ledger at `path`, treating a missing file as an empty
+    // ledger.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents).map_err(|err| Error::from(err).in_file(path)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(Error::Input {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    fn parse(contents: &str) -> Result<Self, ParseError> {
+        let entries = parse::lines(contents, |line| {
+            if line.starts_with('#') {
+                return Ok(None);
+            }
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            if fields.len() != 4 {
+                return Err(ParseError::new(
+                    line,
+                    1,
+                    "expected `day part input-hash answer`",
+                ));
+            }
+            let day = parse::field(line, fields[0], "invalid day")?;
+            let part = parse::field(line, fields[1], "invalid part")?;
+            let hash = u64::from_str_radix(fields[2], 16).map_err(|err| {
+                let column = parse::column(line, fields[2]);
+                ParseError::new(line, column, format!("invalid input hash: {}", err))
+            })?;
+            Ok(Some(((day, part, hash), fields[3].to_string())))
+        })?;
+        Ok(Self {
+            answers: entries.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut contents = String::from("# day part input-hash answer\n");
+        for ((day, part, hash), answer) in &self.answers {
+            contents.push_str(&format!("{} {} {:016x} {}\n", day, part, hash, answer));
+        }
+        fs::write(path, contents).map_err(|source| Error::Output {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn check(&self, day: u32, part: Part, input_hash: u64, answer: &str) -> Status {
+        match self.answers.get(&(day, part.number(), input_hash)) {
+            Some(expected) if expected == answer => Status::Correct,
+            Some(expected) => Status::Wrong {
+                expected: expected.clone(),
+            },
+            None => Status::Unknown,
+        }
+    }
+
+    // Record `answer` as the accepted answer, replacing any previous one.
+    pub fn pin(&mut self, day: u32, part: Part, input_hash: u64, answer: &str) {
+        self.answers
+            .insert((day, part.number(), input_hash), answer.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(hash("1\n2\n"), hash("2\n1\n"));
+    }
+
+    #[test]
+    fn hash_while_reading() {
+        let input = "1721\n979\n366\n299\n675\n1456\n";
+        let mut reader = HashingReader::new(io::BufReader::with_capacity(4, input.as_bytes()));
+        let mut first = String::new();
+        reader.read_line(&mut first).unwrap();
+        assert_eq!(first, "1721\n");
+        assert_eq!(reader.finish().unwrap(), hash(input));
+    }
+
+    #[test]
+    fn check_answers() {
+        let mut ledger = Ledger::parse("# comment\n7 1 00000000000000ff 142\n").unwrap();
+        assert_eq!(ledger.check(7, Part::One, 0xff, "142"), Status::Correct);
+        assert_eq!(
+            ledger.check(7, Part::One, 0xff, "141"),
+            Status::Wrong {
+                expected: "142".into()
+            }
+        );
+        assert_eq!(ledger.check(7, Part::One, 0xfe, "142"), Status::Unknown);
+        assert_eq!(ledger.check(7, Part::Two, 0xff, "142"), Status::Unknown);
+
+        ledger.pin(7, Part::Two, 0xff, "10219");
+        assert_eq!(ledger.check(7, Part::Two, 0xff, "10219"), Status::Correct);
+    }
+
+    #[test]
+    fn malformed_ledger() {
+        let err = Ledger::parse("7 1 xyz 142\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+    }
+}
@@ -0,0 +1,74 @@
+pub mod bench;
+pub mod days;
+pub mod debug;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod json;
+pub mod ledger;
+pub mod parse;
+pub mod runner;
+pub mod scaffold;
+pub mod vm;
+
+use error::{Error, Result};
+use std::{
+    fmt::{self, Display},
+    io::BufRead,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(n: u32) -> Option<Self> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+// Every day implements `Solution`.  The puzzle input is parsed once and
+// then shared by both parts, which is why the parts take the parsed
+// representation by reference rather than the raw input.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    // Parse the input from a stream such as stdin.  Days whose input is
+    // one record per line override this to parse as they read; the rest
+    // read the whole input first.
+    fn parse_reader(reader: &mut dyn BufRead) -> Result<Self::Input> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input).map_err(Error::Io)?;
+        Self::parse(&input)
+    }
+
+    // `None` when the input has no answer, such as a list of expenses with
+    // no pair which sums to 2020.
+    fn part1(input: &Self::Input) -> Option<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Option<Self::Answer2>;
+}
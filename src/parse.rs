@@ -0,0 +1,99 @@
+use crate::error::{Error, ParseError, Result};
+use std::{fmt::Display, io::BufRead, str::FromStr};
+
+// Parse each non-blank line of `input` with `parse_line`, reporting
+// errors against the line they occurred on.
+pub fn lines<T>(
+    input: &str,
+    mut parse_line: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| parse_line(line).map_err(|err| err.on_line(idx + 1)))
+        .collect()
+}
+
+// Like `lines`, but reads and parses the input one line at a time rather
+// than needing all of it up front.
+pub fn read_lines<T>(
+    reader: &mut dyn BufRead,
+    mut parse_line: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>> {
+    let mut results = vec![];
+    let mut line = String::new();
+    let mut line_number = 0;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(Error::Io)? == 0 {
+            break;
+        }
+        line_number += 1;
+
+        let text = line.trim_end_matches(['\n', '\r']);
+        if !text.trim().is_empty() {
+            results.push(parse_line(text).map_err(|err| err.on_line(line_number))?);
+        }
+    }
+
+    Ok(results)
+}
+
+// Parse `field`, which must be a slice of `line`, as a `T`.
+pub fn field<T>(line: &str, field: &str, what: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    field
+        .parse()
+        .map_err(|err| ParseError::new(line, column(line, field), format!("{}: {}", what, err)))
+}
+
+// The 1-based column at which `field`, a slice of `line`, begins.  Falls
+// back to the first column if `field` does not point into `line`.
+pub fn column(line: &str, field: &str) -> usize {
+    let offset = (field.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset <= line.len() && field.len() <= line.len() - offset && line.is_char_boundary(offset) {
+        line[..offset].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_report_their_line() {
+        let input = "1\n\n2\nthree\n";
+        let err = lines(input, |line| field::<u32>(line, line, "invalid number")).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(err.text, "three");
+        assert_eq!(
+            err.to_string(),
+            "line 4:1: invalid number: invalid digit found in string\n    three\n    ^"
+        );
+    }
+
+    #[test]
+    fn read_lines_matches_lines() {
+        let input = "1\r\n\n2\nthree\n";
+        let parse_number = |line: &str| field::<u32>(line, line, "invalid number");
+        let numbers = read_lines(&mut "1\r\n\n2\n".as_bytes(), parse_number).unwrap();
+        assert_eq!(numbers, vec![1, 2]);
+        match read_lines(&mut input.as_bytes(), parse_number) {
+            Err(Error::Parse(err)) => assert_eq!(err, lines(input, parse_number).unwrap_err()),
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn column_of_field() {
+        let line = "1-3 a: abcde";
+        assert_eq!(column(line, &line[2..3]), 3);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    bench::{self, Timing},
+    days, debug,
+    error::Result,
+    json,
+    ledger::Status,
+    Part, Solution,
+};
+use std::{
+    io::BufRead,
+    time::{Duration, Instant},
+};
+
+pub struct Answer {
+    pub day: u32,
+    pub part: Part,
+    // `None` if the input has no answer.
+    pub value: Option<String>,
+    pub elapsed: Duration,
+    // Anything the part reported through `debug!`.
+    pub debug: Vec<String>,
+}
+
+impl Answer {
+    pub fn to_json(&self, status: &Status) -> String {
+        let expected = match status {
+            Status::Wrong { expected } => json::string(expected),
+            _ => String::from("null"),
+        };
+        let status = match status {
+            Status::Correct => "correct",
+            Status::Wrong { .. } => "wrong",
+            Status::Unknown => "unknown",
+        };
+        format!(
+            r#"{{"day":{},"part":{},"answer":{},"status":"{}","expected":{},"elapsed_ns":{},"debug":{}}}"#,
+            self.day,
+            self.part,
+            self.value
+                .as_deref()
+                .map_or(String::from("null"), json::string),
+            status,
+            expected,
+            self.elapsed.as_nanos(),
+            json::array(&self.debug, |line| json::string(line))
+        )
+    }
+}
+
+type Run = fn(u32, &mut dyn BufRead, &[Part]) -> Result<Vec<Answer>>;
+
+// A type-erased handle on a day's `Solution`, so that the runner can pick
+// a day by number at runtime.
+pub struct Day {
+    pub number: u32,
+    run: Run,
+    measure: fn(u32, &str, usize) -> Result<Vec<Timing>>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(number: u32) -> Self {
+        Self {
+            number,
+            run: run::<S>,
+            measure: bench::measure::<S>,
+        }
+    }
+
+    // `input` can be a string too, as `&mut input.as_bytes()`.
+    pub fn run(&self, input: &mut dyn BufRead, parts: &[Part]) -> Result<Vec<Answer>> {
+        (self.run)(self.number, input, parts)
+    }
+
+    pub fn measure(&self, input: &str, iterations: usize) -> Result<Vec<Timing>> {
+        (self.measure)(self.number, input, iterations)
+    }
+}
+
+fn run<S: Solution>(day: u32, input: &mut dyn BufRead, parts: &[Part]) -> Result<Vec<Answer>> {
+    let parsed = S::parse_reader(input)?;
+    Ok(parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let (value, debug) = debug::capture(|| match part {
+                Part::One => S::part1(&parsed).map(|answer| answer.to_string()),
+                Part::Two => S::part2(&parsed).map(|answer| answer.to_string()),
+            });
+            Answer {
+                day,
+                part,
+                value,
+                elapsed: start.elapsed(),
+                debug,
+            }
+        })
+        .collect())
+}
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    days::ALL.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,165 @@
+use crate::error::{Error, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const MODULE_TEMPLATE: &str = r#"use crate::{error::Result, Solution};
+
+pub struct DayN;
+
+impl Solution for DayN {
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    // No answer until the part is written.
+    fn part1(_input: &Self::Input) -> Option<u64> {
+        None
+    }
+
+    fn part2(_input: &Self::Input) -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    #[ignore = "fill in the example from the puzzle"]
+    fn example() {
+        let input = DayN::parse(EXAMPLE).unwrap();
+        assert_eq!(DayN::part1(&input), Some(0));
+        assert_eq!(DayN::part2(&input), Some(0));
+    }
+}
+"#;
+
+// Add a new day to the repository at `root`: its solution module with an
+// example test to fill in, an empty input file, and its entry in the
+// runner.  Returns the files which were created or changed.
+pub fn new_day(root: &Path, day: u32) -> Result<Vec<PathBuf>> {
+    let days_dir = root.join("src").join("days");
+    let mod_path = days_dir.join("mod.rs");
+    let module_path = days_dir.join(format!("day_{}.rs", day));
+    let data_path = root.join("data").join(format!("day_{}.txt", day));
+
+    let mod_rs = fs::read_to_string(&mod_path).map_err(|source| Error::Input {
+        path: mod_path.clone(),
+        source,
+    })?;
+    if module_path.exists() {
+        return Err(Error::Exists(module_path));
+    }
+    let mod_rs = register(&mod_rs, day).ok_or_else(|| Error::Exists(mod_path.clone()))?;
+
+    let module = MODULE_TEMPLATE.replace("DayN", &format!("Day{}", day));
+    write(&module_path, &module)?;
+    write(&mod_path, &mod_rs)?;
+    let mut changed = vec![module_path, mod_path];
+
+    // The input may already have been downloaded.
+    if !data_path.exists() {
+        write(&data_path, "")?;
+        changed.push(data_path);
+    }
+
+    Ok(changed)
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).map_err(|source| Error::Output {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+// Declare the module for `day` in `mod_rs`, the source of `days/mod.rs`,
+// and add it to `ALL`.  Returns `None` if the day is already registered.
+fn register(mod_rs: &str, day: u32) -> Option<String> {
+    let module = format!("day_{}", day);
+    let declaration = format!("pub mod {};", module);
+    let entry = format!("    Day::new::<{}::Day{}>({}),", module, day, day);
+    if mod_rs.lines().any(|line| line == declaration) {
+        return None;
+    }
+
+    let mut lines: Vec<String> = mod_rs.lines().map(String::from).collect();
+
+    // Module declarations are kept in the order rustfmt sorts them.
+    let declarations = lines.iter().position(|l| l.starts_with("pub mod "))?;
+    let position = lines[declarations..]
+        .iter()
+        .position(|l| match l.strip_prefix("pub mod ") {
+            Some(name) => name.trim_end_matches(';') > module.as_str(),
+            None => true,
+        })
+        .map_or(lines.len(), |offset| declarations + offset);
+    lines.insert(position, declaration);
+
+    // `ALL` is ordered by day.
+    let start = lines.iter().position(|l| l.starts_with("pub const ALL"))?;
+    let end = start + lines[start..].iter().position(|l| l == "];")?;
+    let position = (start + 1..end)
+        .find(|&idx| entry_day(&lines[idx]).is_some_and(|d| d > day))
+        .unwrap_or(end);
+    lines.insert(position, entry);
+
+    Some(lines.join("\n") + "\n")
+}
+
+fn entry_day(line: &str) -> Option<u32> {
+    let start = line.rfind('(')? + 1;
+    let end = line.rfind(')')?;
+    line.get(start..end)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_new_day() {
+        let mod_rs = include_str!("days/mod.rs");
+        let registered = register(mod_rs, 15).unwrap();
+        assert!(registered.contains("pub mod day_14;\npub mod day_15;\npub mod day_2;\n"));
+        assert!(registered.contains(
+            "    Day::new::<day_14::Day14>(14),\n    Day::new::<day_15::Day15>(15),\n];\n"
+        ));
+        assert_eq!(register(&registered, 15), None);
+        assert_eq!(register(mod_rs, 7), None);
+    }
+
+    #[test]
+    fn creates_files() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src").join("days")).unwrap();
+        fs::create_dir_all(root.join("data")).unwrap();
+        fs::write(
+            root.join("src").join("days").join("mod.rs"),
+            include_str!("days/mod.rs"),
+        )
+        .unwrap();
+
+        let changed = new_day(&root, 21).unwrap();
+        assert_eq!(changed.len(), 3);
+        let module = fs::read_to_string(root.join("src/days/day_21.rs")).unwrap();
+        assert!(module.contains("impl Solution for Day21 {"));
+        assert_eq!(
+            fs::read_to_string(root.join("data/day_21.txt")).unwrap(),
+            ""
+        );
+        assert!(matches!(new_day(&root, 21), Err(Error::Exists(_))));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
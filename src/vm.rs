@@ -0,0 +1,544 @@
+use crate::{error::ParseError, parse};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt,
+};
+
+// A general version of day 8's handheld console, for puzzles which need
+// more than an accumulator.  Opcodes are registered with an
+// `InstructionSet`, from which the parser, the executor and the
+// disassembler all work, so adding an instruction doesn't mean editing
+// any of them.
+
+// What an operand of an opcode may be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    // The name of a register.
+    Register,
+    // A number, written with an optional sign.
+    Number,
+    // Either of the above, standing for the register's value.
+    Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operand {
+    Register(String),
+    Number(i64),
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Register(name) => f.write_str(name),
+            Operand::Number(n) => write!(f, "{:+}", n),
+        }
+    }
+}
+
+// Where execution goes after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    // Relative to the instruction, like `jmp`.
+    Jump(i64),
+    Halt,
+}
+
+// Why an instruction couldn't run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    NoInput,
+    BadAddress(i64),
+    // A result didn't fit in 64 bits.
+    Overflow,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fault::NoInput => f.write_str("no input left to read"),
+            Fault::BadAddress(address) => write!(f, "bad memory address {}", address),
+            Fault::Overflow => f.write_str("arithmetic overflow"),
+        }
+    }
+}
+
+pub trait Opcode {
+    fn mnemonic(&self) -> &str;
+    fn operands(&self) -> &[Kind];
+    // Carry out the instruction.  `operands` match `operands()`, which the
+    // parser has already checked.
+    fn execute(&self, machine: &mut Machine, operands: &[Operand]) -> Result<Flow, Fault>;
+}
+
+// An opcode defined by a function, which is all most need.
+pub struct Simple {
+    pub mnemonic: &'static str,
+    pub operands: &'static [Kind],
+    pub execute: fn(&mut Machine, &[Operand]) -> Result<Flow, Fault>,
+}
+
+impl Opcode for Simple {
+    fn mnemonic(&self) -> &str {
+        self.mnemonic
+    }
+
+    fn operands(&self) -> &[Kind] {
+        self.operands
+    }
+
+    fn execute(&self, machine: &mut Machine, operands: &[Operand]) -> Result<Flow, Fault> {
+        (self.execute)(machine, operands)
+    }
+}
+
+// The registers, memory and I/O of a running program.  Registers and
+// memory cells start at 0 and exist as soon as they are used.
+#[derive(Debug, Default, Clone)]
+pub struct Machine {
+    pub pc: i64,
+    pub registers: BTreeMap<String, i64>,
+    pub memory: BTreeMap<i64, i64>,
+    pub input: VecDeque<i64>,
+    pub output: Vec<i64>,
+}
+
+impl Machine {
+    pub fn register(&self, name: &str) -> i64 {
+        self.registers.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn register_mut(&mut self, operand: &Operand) -> &mut i64 {
+        match operand {
+            Operand::Register(name) => self.registers.entry(name.clone()).or_insert(0),
+            Operand::Number(_) => panic!("{} is not a register", operand),
+        }
+    }
+
+    pub fn value(&self, operand: &Operand) -> i64 {
+        match operand {
+            Operand::Register(name) => self.register(name),
+            Operand::Number(n) => *n,
+        }
+    }
+
+    pub fn load(&self, address: i64) -> Result<i64, Fault> {
+        match address {
+            a if a < 0 => Err(Fault::BadAddress(a)),
+            a => Ok(self.memory.get(&a).copied().unwrap_or(0)),
+        }
+    }
+
+    pub fn store(&mut self, address: i64, value: i64) -> Result<(), Fault> {
+        match address {
+            a if a < 0 => Err(Fault::BadAddress(a)),
+            a => {
+                self.memory.insert(a, value);
+                Ok(())
+            }
+        }
+    }
+
+    // Run the instruction at `pc`, or say why the program has stopped.
+    pub fn step(&mut self, program: &[Op<'_>]) -> Option<Exit> {
+        let op = match self.pc {
+            pc if pc == program.len() as i64 => return Some(Exit::Halted),
+            pc if pc < 0 || pc > program.len() as i64 => return Some(Exit::OutOfBounds { pc }),
+            pc => &program[pc as usize],
+        };
+        match self.execute(op) {
+            Ok(Flow::Halt) => {
+                self.pc = program.len() as i64;
+                Some(Exit::Halted)
+            }
+            Ok(_) => None,
+            Err(fault) => Some(Exit::Fault { pc: self.pc, fault }),
+        }
+    }
+
+    // Run `op` as the instruction at `pc` and move `pc` on, unless `op`
+    // halts or faults.
+    pub fn execute(&mut self, op: &Op<'_>) -> Result<Flow, Fault> {
+        let flow = op.opcode.execute(self, &op.operands)?;
+        let offset = match flow {
+            Flow::Next => 1,
+            Flow::Jump(offset) => offset,
+            Flow::Halt => 0,
+        };
+        self.pc = self.pc.checked_add(offset).ok_or(Fault::Overflow)?;
+        Ok(flow)
+    }
+
+    // Step until the program stops or `fuel` instructions have run.
+    pub fn run(&mut self, program: &[Op<'_>], fuel: usize) -> Exit {
+        for _ in 0..fuel {
+            if let Some(exit) = self.step(program) {
+                return exit;
+            }
+        }
+        match self.pc {
+            pc if pc == program.len() as i64 => Exit::Halted,
+            _ => Exit::FuelExhausted,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exit {
+    Halted,
+    OutOfBounds { pc: i64 },
+    Fault { pc: i64, fault: Fault },
+    FuelExhausted,
+}
+
+// An instruction: an opcode from an `InstructionSet` and its operands.
+#[derive(Clone)]
+pub struct Op<'a> {
+    pub opcode: &'a dyn Opcode,
+    pub operands: Vec<Operand>,
+}
+
+impl fmt::Display for Op<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.opcode.mnemonic())?;
+        for operand in &self.operands {
+            write!(f, " {}", operand)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Op<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+#[derive(Default)]
+pub struct InstructionSet {
+    opcodes: Vec<Box<dyn Opcode>>,
+}
+
+impl InstructionSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Add `opcode`, replacing any with the same mnemonic.
+    pub fn with(mut self, opcode: impl Opcode + 'static) -> Self {
+        self.opcodes
+            .retain(|existing| existing.mnemonic() != opcode.mnemonic());
+        self.opcodes.push(Box::new(opcode));
+        self
+    }
+
+    // Day 8's `acc`, `jmp` and `nop`, with the accumulator in register
+    // `acc`.
+    pub fn handheld() -> Self {
+        Self::new().with(ACC).with(JMP).with(NOP)
+    }
+
+    // The handheld set plus general registers, conditional jumps, memory
+    // and I/O.
+    pub fn extended() -> Self {
+        Self::handheld()
+            .with(SET)
+            .with(ADD)
+            .with(MUL)
+            .with(JNZ)
+            .with(JGZ)
+            .with(LOAD)
+            .with(STORE)
+            .with(IN)
+            .with(OUT)
+            .with(HLT)
+    }
+
+    pub fn get(&self, mnemonic: &str) -> Option<&dyn Opcode> {
+        self.opcodes
+            .iter()
+            .find(|opcode| opcode.mnemonic() == mnemonic)
+            .map(|opcode| opcode.as_ref())
+    }
+
+    // Parse one instruction per line, as a mnemonic followed by its
+    // operands separated by spaces.
+    pub fn parse<'a>(&'a self, input: &str) -> Result<Vec<Op<'a>>, ParseError> {
+        parse::lines(input, |line| {
+            let mut fields = line.split_whitespace();
+            let mnemonic = fields.next().unwrap_or_default();
+            let opcode = self.get(mnemonic).ok_or_else(|| {
+                ParseError::new(
+                    line,
+                    parse::column(line, mnemonic),
+                    format!("unknown instruction `{}`", mnemonic),
+                )
+            })?;
+
+            let fields: Vec<&str> = fields.collect();
+            if fields.len() != opcode.operands().len() {
+                return Err(ParseError::new(
+                    line,
+                    parse::column(line, mnemonic),
+                    format!(
+                        "`{}` takes {} operand(s), found {}",
+                        mnemonic,
+                        opcode.operands().len(),
+                        fields.len()
+                    ),
+                ));
+            }
+            let operands = opcode
+                .operands()
+                .iter()
+                .zip(fields)
+                .map(|(&kind, field)| operand(line, field, kind))
+                .collect::<Result<_, _>>()?;
+
+            Ok(Op { opcode, operands })
+        })
+    }
+}
+
+// Write a program out in the form `InstructionSet::parse` reads.
+pub fn disassemble(program: &[Op<'_>]) -> String {
+    program.iter().map(|op| format!("{}\n", op)).collect()
+}
+
+fn operand(line: &str, field: &str, kind: Kind) -> Result<Operand, ParseError> {
+    let is_number = field.starts_with(|c: char| c == '+' || c == '-' || c.is_ascii_digit());
+    let is_register = field.starts_with(|c: char| c.is_ascii_alphabetic())
+        && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    match kind {
+        Kind::Number | Kind::Value if is_number => {
+            parse::field(line, field, "invalid number").map(Operand::Number)
+        }
+        Kind::Register | Kind::Value if is_register => Ok(Operand::Register(field.to_string())),
+        _ => Err(ParseError::new(
+            line,
+            parse::column(line, field),
+            match kind {
+                Kind::Register => "expected a register",
+                Kind::Number => "expected a number",
+                Kind::Value => "expected a register or a number",
+            },
+        )),
+    }
+}
+
+pub const ACC: Simple = Simple {
+    mnemonic: "acc",
+    operands: &[Kind::Number],
+    execute: |machine, operands| {
+        let value = machine.value(&operands[0]);
+        let acc = machine.register_mut(&Operand::Register(String::from("acc")));
+        *acc = acc.checked_add(value).ok_or(Fault::Overflow)?;
+        Ok(Flow::Next)
+    },
+};
+
+pub const JMP: Simple = Simple {
+    mnemonic: "jmp",
+    operands: &[Kind::Number],
+    execute: |machine, operands| Ok(Flow::Jump(machine.value(&operands[0]))),
+};
+
+pub const NOP: Simple = Simple {
+    mnemonic: "nop",
+    operands: &[Kind::Number],
+    execute: |_, _| Ok(Flow::Next),
+};
+
+pub const SET: Simple = Simple {
+    mnemonic: "set",
+    operands: &[Kind::Register, Kind::Value],
+    execute: |machine, operands| {
+        *machine.register_mut(&operands[0]) = machine.value(&operands[1]);
+        Ok(Flow::Next)
+    },
+};
+
+pub const ADD: Simple = Simple {
+    mnemonic: "add",
+    operands: &[Kind::Register, Kind::Value],
+    execute: |machine, operands| {
+        let value = machine.value(&operands[1]);
+        let register = machine.register_mut(&operands[0]);
+        *register = register.checked_add(value).ok_or(Fault::Overflow)?;
+        Ok(Flow::Next)
+    },
+};
+
+pub const MUL: Simple = Simple {
+    mnemonic: "mul",
+    operands: &[Kind::Register, Kind::Value],
+    execute: |machine, operands| {
+        let value = machine.value(&operands[1]);
+        let register = machine.register_mut(&operands[0]);
+        *register = register.checked_mul(value).ok_or(Fault::Overflow)?;
+        Ok(Flow::Next)
+    },
+};
+
+// Jump by the second operand if the first is not zero.
+pub const JNZ: Simple = Simple {
+    mnemonic: "jnz",
+    operands: &[Kind::Value, Kind::Value],
+    execute: |machine, operands| match machine.value(&operands[0]) {
+        0 => Ok(Flow::Next),
+        _ => Ok(Flow::Jump(machine.value(&operands[1]))),
+    },
+};
+
+// Jump by the second operand if the first is greater than zero.
+pub const JGZ: Simple = Simple {
+    mnemonic: "jgz",
+    operands: &[Kind::Value, Kind::Value],
+    execute: |machine, operands| match machine.value(&operands[0]) {
+        v if v > 0 => Ok(Flow::Jump(machine.value(&operands[1]))),
+        _ => Ok(Flow::Next),
+    },
+};
+
+// `load r a` sets `r` to the memory cell at address `a`.
+pub const LOAD: Simple = Simple {
+    mnemonic: "load",
+    operands: &[Kind::Register, Kind::Value],
+    execute: |machine, operands| {
+        let value = machine.load(machine.value(&operands[1]))?;
+        *machine.register_mut(&operands[0]) = value;
+        Ok(Flow::Next)
+    },
+};
+
+// `store v a` sets the memory cell at address `a` to `v`.
+pub const STORE: Simple = Simple {
+    mnemonic: "store",
+    operands: &[Kind::Value, Kind::Value],
+    execute: |machine, operands| {
+        machine.store(machine.value(&operands[1]), machine.value(&operands[0]))?;
+        Ok(Flow::Next)
+    },
+};
+
+pub const IN: Simple = Simple {
+    mnemonic: "in",
+    operands: &[Kind::Register],
+    execute: |machine, operands| {
+        let value = machine.input.pop_front().ok_or(Fault::NoInput)?;
+        *machine.register_mut(&operands[0]) = value;
+        Ok(Flow::Next)
+    },
+};
+
+pub const OUT: Simple = Simple {
+    mnemonic: "out",
+    operands: &[Kind::Value],
+    execute: |machine, operands| {
+        let value = machine.value(&operands[0]);
+        machine.output.push(value);
+        Ok(Flow::Next)
+    },
+};
+
+pub const HLT: Simple = Simple {
+    mnemonic: "hlt",
+    operands: &[],
+    execute: |_, _| Ok(Flow::Halt),
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn runs_day_8() {
+        let set = InstructionSet::handheld();
+        let input = include_str!("../data/day_8.txt");
+        let program = set.parse(input).unwrap();
+        assert_eq!(disassemble(&program).trim_end(), input.trim_end());
+
+        // Without the other instructions, a repeated pc means a loop.
+        let mut machine = Machine::default();
+        let mut executed = HashSet::new();
+        while executed.insert(machine.pc) {
+            assert_eq!(machine.step(&program), None);
+        }
+        assert_eq!(machine.register("acc"), 1394);
+    }
+
+    #[test]
+    fn extended_instructions() {
+        // Output the factorials of each input until a 0.
+        let set = InstructionSet::extended();
+        let program = set
+            .parse(
+                "in n\njnz n +2\nhlt\nset f 1\nmul f n\nadd n -1\njgz n -2\nstore f 7\nload g 7\nout g\njmp -10\n",
+            )
+            .unwrap();
+        let mut machine = Machine {
+            input: vec![3, 5, 0].into(),
+            ..Machine::default()
+        };
+        assert_eq!(machine.run(&program, 1000), Exit::Halted);
+        assert_eq!(machine.output, vec![6, 120]);
+
+        let mut machine = Machine::default();
+        assert_eq!(
+            machine.run(&program, 1000),
+            Exit::Fault {
+                pc: 0,
+                fault: Fault::NoInput
+            }
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let set = InstructionSet::extended();
+        for (source, pc) in &[
+            ("set a 9223372036854775807\nadd a 1\n", 1),
+            ("set a 4611686018427387904\nmul a 2\n", 1),
+            ("acc -9223372036854775808\nacc -1\n", 1),
+            ("jmp +1\njmp 9223372036854775807\n", 1),
+        ] {
+            let program = set.parse(source).unwrap();
+            let mut machine = Machine::default();
+            assert_eq!(
+                machine.run(&program, 10),
+                Exit::Fault {
+                    pc: *pc,
+                    fault: Fault::Overflow
+                },
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn new_opcodes() {
+        // Registering an opcode is enough for the parser, the executor and
+        // the disassembler.
+        let set = InstructionSet::handheld().with(Simple {
+            mnemonic: "dbl",
+            operands: &[Kind::Register],
+            execute: |machine, operands| {
+                *machine.register_mut(&operands[0]) *= 2;
+                Ok(Flow::Next)
+            },
+        });
+        let program = set.parse("acc +3\ndbl acc\n").unwrap();
+        let mut machine = Machine::default();
+        assert_eq!(machine.run(&program, 10), Exit::Halted);
+        assert_eq!(machine.register("acc"), 6);
+        assert_eq!(disassemble(&program), "acc +3\ndbl acc\n");
+
+        let err = set.parse("acc +3\ndbl 2\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        let err = set.parse("mul acc 2\n").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}